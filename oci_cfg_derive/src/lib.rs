@@ -0,0 +1,104 @@
+//! The oci_cfg_derive crate provides `#[derive(OciProfile)]` for structs that hold a profile of the OCI config file.
+//! It is re-exported by `oci_cfg` with the `derive` feature and implements `oci_cfg::typed::OciProfile` with a validation generated from the `#[oci(...)]` attributes of the fields:
+//! `#[oci(ocid = "compartment")]` expects an OCID of a resource type, `#[oci(region)]` a region identifier and `#[oci(path)]` a file path, which has to exist with `#[oci(path, must_exist)]`.
+//! Fields are `String`, `PathBuf` or an `Option` of them, `None` isn't validated.
+//! # Example
+//! ```rust,ignore
+//! use oci_cfg::typed::OciProfile;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, OciProfile)]
+//! struct Profile {
+//!     #[oci(ocid = "tenancy")]
+//!     tenancy: String,
+//!     #[oci(region)]
+//!     region: String,
+//!     #[oci(ocid = "compartment")]
+//!     compartment_id: Option<String>,
+//!     #[oci(path, must_exist)]
+//!     key_file: String,
+//! }
+//!
+//! let profile = Profile::load("DEV").unwrap();
+//! ```
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// implements `oci_cfg::typed::OciProfile` for a struct with named fields, validating the fields by their `#[oci(...)]` attributes.
+#[proc_macro_derive(OciProfile, attributes(oci))]
+pub fn derive_oci_profile(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+// Function to generate the implementation of the trait
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "OciProfile needs a struct with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "OciProfile can only be derived for structs")),
+    };
+
+    let mut checks = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named fields have an ident");
+        let name = ident.to_string();
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("oci")) {
+            let mut ocid: Option<LitStr> = None;
+            let mut region = false;
+            let mut path = false;
+            let mut must_exist = false;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("ocid") {
+                    ocid = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("region") {
+                    region = true;
+                } else if meta.path.is_ident("path") {
+                    path = true;
+                } else if meta.path.is_ident("must_exist") {
+                    must_exist = true;
+                } else {
+                    return Err(meta.error("unknown oci attribute, use ocid = \"...\", region, path or must_exist"));
+                }
+                Ok(())
+            })?;
+            if must_exist && !path {
+                return Err(syn::Error::new_spanned(attr, "must_exist is only allowed together with path"));
+            }
+
+            let mut calls = Vec::new();
+            if let Some(resource_type) = ocid {
+                calls.push(quote! { ::oci_cfg::typed::check_ocid(#name, value, #resource_type)?; });
+            }
+            if region {
+                calls.push(quote! { ::oci_cfg::typed::check_region(#name, value)?; });
+            }
+            if path {
+                calls.push(quote! { ::oci_cfg::typed::check_path(#name, value, #must_exist)?; });
+            }
+            checks.push(quote! {
+                if let ::std::option::Option::Some(value) = ::oci_cfg::typed::Field::value(&self.#ident) {
+                    #(#calls)*
+                }
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::oci_cfg::typed::OciProfile for #ident #type_generics #where_clause {
+            fn validate(&self) -> ::std::io::Result<()> {
+                #(#checks)*
+                ::std::result::Result::Ok(())
+            }
+        }
+    })
+}
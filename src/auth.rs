@@ -0,0 +1,291 @@
+//! The auth module unifies the credential sources of the crate behind the 'AuthProvider' trait.
+//! API key and session profiles, resource principals and instance principals sign requests the same way, so a client doesn't need to know where its credentials come from.
+//! The default provider chain tries an explicit profile, the `OCI_CLI_*` environment variables, the resource principal and the instance principal, in this order.
+//! Without a transport for the instance principal, the DEFAULT profile of the config file is tried last.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::auth::{default_provider, AuthProvider};
+//!
+//! let provider = default_provider().unwrap();
+//! let mut headers: Vec<(String, String)> = Vec::new();
+//! provider.sign("GET", "https://iaas.us-ashburn-1.oraclecloud.com/20160918/vcns", &mut headers, b"").unwrap();
+//! println!("Signed for region {:?}", provider.region());
+//! ```
+use crate::cli_rc::RC_NAME;
+use crate::config::{Config, ResolvedProfile, DEFAULT_PROFILE};
+use crate::principal::{InstancePrincipal, ResourcePrincipal, METADATA_URL, VERSION};
+use crate::resolve::{Layer, Resolver};
+use crate::session::SessionToken;
+use crate::signer::Signer;
+use crate::{DIR, NAME};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use pkcs8::EncodePublicKey;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use std::env;
+use std::io;
+
+/// name of the variable holding the user OCID of a profile defined by the environment.
+pub static ENV_USER: &str = "OCI_CLI_USER";
+/// name of the variable holding the tenancy OCID of a profile defined by the environment.
+pub static ENV_TENANCY: &str = "OCI_CLI_TENANCY";
+/// name of the variable holding the key fingerprint of a profile defined by the environment.
+pub static ENV_FINGERPRINT: &str = "OCI_CLI_FINGERPRINT";
+/// name of the variable holding the key file of a profile defined by the environment.
+pub static ENV_KEY_FILE: &str = "OCI_CLI_KEY_FILE";
+/// name of the variable holding the region of a profile defined by the environment.
+pub static ENV_REGION: &str = "OCI_CLI_REGION";
+
+/// sends a POST request with the given URL, headers and body and returns the response body.
+/// The crate doesn't depend on an HTTP client, the transport is provided by the caller.
+pub type Transport = Box<dyn Fn(&str, &[(String, String)], &str) -> io::Result<String>>;
+
+/// provides the identity that signs requests against the OCI API.
+pub trait AuthProvider {
+    /// returns the key id sent with every signature.
+    fn key_id(&self) -> io::Result<String>;
+    /// signs a request and adds the authorization header to the given headers.
+    fn sign(&self, method: &str, url: &str, headers: &mut Vec<(String, String)>, body: &[u8]) -> io::Result<()>;
+    /// returns the region the credentials belong to.
+    fn region(&self) -> Option<String>;
+    /// reloads rotated keys and renews tokens.
+    fn refresh(&mut self) -> io::Result<()>;
+}
+
+/// provides the identity of a resolved profile, from a config file, the environment or a resource principal.
+pub struct ProfileProvider {
+    profile: ResolvedProfile,
+    signer: Signer,
+}
+
+impl ProfileProvider {
+    /// creates a provider from a resolved profile, the key and the token are read once.
+    pub fn new(profile: ResolvedProfile) -> io::Result<ProfileProvider> {
+        let signer = Signer::from_profile(&profile)?;
+        Ok(ProfileProvider { profile, signer })
+    }
+
+    /// returns the profile behind the provider.
+    pub fn profile(&self) -> &ResolvedProfile {
+        &self.profile
+    }
+}
+
+impl AuthProvider for ProfileProvider {
+    fn key_id(&self) -> io::Result<String> {
+        Ok(self.signer.key_id().to_string())
+    }
+
+    fn sign(&self, method: &str, url: &str, headers: &mut Vec<(String, String)>, body: &[u8]) -> io::Result<()> {
+        self.signer.sign(method, url, headers, body)
+    }
+
+    fn region(&self) -> Option<String> {
+        self.profile.region.clone()
+    }
+
+    fn refresh(&mut self) -> io::Result<()> {
+        self.signer = Signer::from_profile(&self.profile)?;
+        Ok(())
+    }
+}
+
+/// provides the identity of a compute instance. The leaf certificate of the instance is exchanged for a security token at the federation endpoint,
+/// the token then signs requests together with a session key that is generated by the provider.
+pub struct InstancePrincipalProvider {
+    metadata_url: String,
+    transport: Transport,
+    principal: InstancePrincipal,
+    session: Option<(SessionToken, Signer)>,
+}
+
+impl InstancePrincipalProvider {
+    /// creates a provider from the metadata service at the given base URL and requests the first token.
+    pub fn new(metadata_url: &str, transport: Transport) -> io::Result<InstancePrincipalProvider> {
+        let mut provider = InstancePrincipalProvider {
+            metadata_url: metadata_url.to_string(),
+            transport,
+            principal: InstancePrincipal::from_url(metadata_url)?,
+            session: None,
+        };
+        provider.refresh()?;
+        Ok(provider)
+    }
+
+    /// returns the instance principal behind the provider.
+    pub fn principal(&self) -> &InstancePrincipal {
+        &self.principal
+    }
+
+    // Function to exchange the leaf certificate for a security token bound to the session key
+    fn federate(&self, session_key: &RsaPublicKey) -> io::Result<SessionToken> {
+        let url = format!("https://auth.{}.oraclecloud.com/v1/x509", self.principal.region);
+        let public_key = session_key
+            .to_public_key_der()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+        let body = serde_json::json!({
+            "certificate": pem_content(&self.principal.certificate),
+            "intermediateCertificates": [pem_content(&self.principal.intermediate_certificate)],
+            "publicKey": STANDARD.encode(public_key.as_bytes()),
+            "purpose": "DEFAULT",
+        })
+        .to_string();
+
+        let mut headers = vec![(String::from("content-type"), String::from("application/json"))];
+        let leaf = Signer::new(&self.principal.key_id()?, self.principal.profile()?.signing_key()?);
+        leaf.sign("POST", &url, &mut headers, body.as_bytes())?;
+
+        let response = (self.transport)(&url, &headers, &body)?;
+        let response: serde_json::Value = serde_json::from_str(&response)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+        let token = response["token"]
+            .as_str()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "The federation response contains no token"))?;
+        SessionToken::parse(token)
+    }
+
+    // Function to return the current session, failing when it is missing or expired
+    fn session(&self) -> io::Result<&(SessionToken, Signer)> {
+        match &self.session {
+            Some(session) if !session.0.is_expired() => Ok(session),
+            _ => Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "The instance principal token has expired, refresh the provider",
+            )),
+        }
+    }
+}
+
+impl AuthProvider for InstancePrincipalProvider {
+    fn key_id(&self) -> io::Result<String> {
+        Ok(self.session()?.1.key_id().to_string())
+    }
+
+    fn sign(&self, method: &str, url: &str, headers: &mut Vec<(String, String)>, body: &[u8]) -> io::Result<()> {
+        self.session()?.1.sign(method, url, headers, body)
+    }
+
+    fn region(&self) -> Option<String> {
+        Some(self.principal.region.clone())
+    }
+
+    fn refresh(&mut self) -> io::Result<()> {
+        // certificates rotate, read them again before requesting a new token
+        self.principal = InstancePrincipal::from_url(&self.metadata_url)?;
+        let session_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+        let token = self.federate(&RsaPublicKey::from(&session_key))?;
+        let signer = Signer::new(&token.key_id(), session_key);
+        self.session = Some((token, signer));
+        Ok(())
+    }
+}
+
+/// contains the options of the default provider chain.
+/// # Example
+/// ```rust,no_run
+/// use oci_cfg::auth::ProviderChain;
+///
+/// let mut chain = ProviderChain::new();
+/// chain.profile = Some(String::from("ADMIN_USER"));
+/// let provider = chain.provider().unwrap();
+/// ```
+pub struct ProviderChain {
+    pub profile: Option<String>,
+    pub config_file: String,
+    pub cli_rc_file: String,
+    pub metadata_url: String,
+    pub transport: Option<Transport>,
+}
+
+impl ProviderChain {
+    pub fn new() -> Self {
+        Self {
+            profile: None,
+            config_file: format!("{}/{}", DIR, NAME),
+            cli_rc_file: format!("{}/{}", DIR, RC_NAME),
+            metadata_url: METADATA_URL.to_string(),
+            transport: None,
+        }
+    }
+
+    /// returns the first provider that can be created. A profile selected by the chain, `OCI_CLI_PROFILE` or the `default_profile` of `oci_cli_rc`
+    /// fails the chain when it can't be used. Without a selected profile the environment, the resource principal and the instance principal are tried,
+    /// instance principals require a transport. Without transport the DEFAULT profile of the config file takes the place of the instance principal.
+    /// Without a changed config file, `OCI_CLI_CONFIG_FILE` selects the config file like in the OCI CLI.
+    pub fn provider(self) -> io::Result<Box<dyn AuthProvider>> {
+        // explicitly selected profile from the config file
+        let mut resolver = Resolver::new();
+        resolver.config_file = self.config_file.clone();
+        resolver.cli_rc_file = self.cli_rc_file.clone();
+        resolver.profile = self.profile.clone();
+        let resolution = resolver.resolve()?;
+        if resolution.profile_origin.layer != Layer::Default {
+            return Ok(Box::new(ProfileProvider::new(resolution.profile())?));
+        }
+
+        // profile defined by environment variables
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        if let (Some(user), Some(tenancy), Some(fingerprint), Some(key_file)) =
+            (var(ENV_USER), var(ENV_TENANCY), var(ENV_FINGERPRINT), var(ENV_KEY_FILE))
+        {
+            let profile = ResolvedProfile {
+                name: String::from("environment"),
+                user: Some(user),
+                tenancy: Some(tenancy),
+                fingerprint: Some(fingerprint),
+                key_file: Some(key_file),
+                region: var(ENV_REGION),
+                ..Default::default()
+            };
+            return Ok(Box::new(ProfileProvider::new(profile)?));
+        }
+
+        // resource principal of a function
+        if var(VERSION).is_some() {
+            let profile = ResourcePrincipal::from_env()?.profile()?;
+            return Ok(Box::new(ProfileProvider::new(profile)?));
+        }
+
+        // instance principal of a compute instance
+        if let Some(transport) = self.transport {
+            return Ok(Box::new(InstancePrincipalProvider::new(&self.metadata_url, transport)?));
+        }
+
+        // DEFAULT profile of the config file
+        match Config::load(&resolver.config_file_with(|name| env::var(name).ok())?) {
+            Ok(config) if config.profiles().iter().any(|profile| profile == DEFAULT_PROFILE) => {
+                Ok(Box::new(ProfileProvider::new(resolution.profile())?))
+            }
+            Ok(_) => Err(no_credentials()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Err(no_credentials()),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl Default for ProviderChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// returns the first provider of the default chain. The crate has no HTTP client, so the default chain skips instance principals,
+/// create a `ProviderChain` with a transport to include them.
+pub fn default_provider() -> io::Result<Box<dyn AuthProvider>> {
+    ProviderChain::new().provider()
+}
+
+// Function to create the error of a chain without usable credentials
+fn no_credentials() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        "No credentials found in the environment, the resource principal or the DEFAULT profile of the config file",
+    )
+}
+
+// Function to strip the armor of a PEM document, the federation endpoint expects the base64 content
+fn pem_content(pem: &str) -> String {
+    pem.lines().filter(|line| !line.starts_with("-----")).collect()
+}
+
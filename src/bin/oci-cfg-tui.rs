@@ -0,0 +1,18 @@
+//! The oci-cfg-tui binary browses and edits the profiles of the OCI config file in a full-screen terminal UI, built on the tui module.
+//! Usage: `oci-cfg-tui [config file]`, keys: Tab switches the pane, Enter edits and saves, Esc cancels, q quits.
+use oci_cfg::tui::{run, App};
+use std::env;
+use std::io;
+
+fn main() -> io::Result<()> {
+    // a config file given on the command line is resolved against the working directory
+    let config_file = match env::args().nth(1) {
+        Some(path) => env::current_dir()?.join(path).to_string_lossy().to_string(),
+        None => String::from(".oci/config"),
+    };
+    let mut app = App::new(config_file)?;
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,373 @@
+//! The oci-cfg binary manages the OCI config file from the command line, built on the library API.
+//! Every command reads the file in the user's home, the file in `OCI_CLI_CONFIG_FILE` or the file given with `--config`, and prints JSON with `--json`.
+//! Relative paths in `OCI_CLI_CONFIG_FILE` and `--config` are resolved against the working directory.
+//! Shells complete subcommands, profile names and regions after registering the completion, e.g. `source <(COMPLETE=bash oci-cfg)`.
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use clap_complete::CompleteEnv;
+use oci_cfg::cli_rc::{active_profile, RC_NAME};
+use oci_cfg::config::{Config, DEFAULT_PROFILE};
+use oci_cfg::diagnostic::check;
+use oci_cfg::doctor::{diagnose_config, Severity};
+use oci_cfg::export::{export_env, Shell};
+use oci_cfg::import::Importer;
+use oci_cfg::lint::lint;
+use oci_cfg::region::{codes, identifiers, lookup};
+use oci_cfg::resolve::Resolver;
+use oci_cfg::terraform::{self, Provider};
+use directories::UserDirs;
+use serde_json::json;
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "oci-cfg", version, about = "Manage the profiles of the OCI config file")]
+struct Cli {
+    /// config file to use instead of ~/.oci/config
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// print JSON for scripting
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// list the profiles, the active profile is marked with *
+    List,
+    /// show the resolved values of a profile and where they come from
+    Show {
+        #[arg(add = ArgValueCandidates::new(profiles))]
+        profile: Option<String>,
+    },
+    /// set a key of a profile
+    Set {
+        #[arg(add = ArgValueCandidates::new(profiles))]
+        profile: String,
+        key: String,
+        value: String,
+    },
+    /// add a profile
+    Add {
+        profile: String,
+        #[arg(long)]
+        user: String,
+        #[arg(long)]
+        fingerprint: String,
+        #[arg(long)]
+        key_file: String,
+        #[arg(long)]
+        tenancy: String,
+        #[arg(long, add = ArgValueCandidates::new(regions))]
+        region: String,
+    },
+    /// remove a profile
+    Remove {
+        #[arg(add = ArgValueCandidates::new(profiles))]
+        profile: String,
+    },
+    /// rename a profile
+    Rename {
+        #[arg(add = ArgValueCandidates::new(profiles))]
+        profile: String,
+        new_name: String,
+    },
+    /// list the region identifiers
+    Regions,
+    /// report malformed lines and lint findings of the config file
+    Validate,
+    /// diagnose a profile without contacting OCI
+    Doctor {
+        #[arg(add = ArgValueCandidates::new(profiles))]
+        profile: Option<String>,
+    },
+    /// print statements that export a profile as OCI_CLI_* and TF_VAR_* variables, e.g. eval "$(oci-cfg env PROD)"
+    Env {
+        #[arg(add = ArgValueCandidates::new(profiles))]
+        profile: Option<String>,
+        /// bash, zsh, fish or powershell
+        #[arg(long, default_value = "bash")]
+        shell: Shell,
+    },
+    /// print the Terraform provider block of a profile with its variable declarations
+    Terraform {
+        #[arg(add = ArgValueCandidates::new(profiles))]
+        profile: Option<String>,
+        /// read the fields from variables instead of the config file profile
+        #[arg(long)]
+        variables: bool,
+        /// print the terraform.tfvars of the profile instead
+        #[arg(long)]
+        tfvars: bool,
+    },
+    /// import a profile from the TF_VAR_* variables and tfvars files
+    Import {
+        profile: String,
+        /// tfvars file to read after the environment, later files override earlier values
+        #[arg(long)]
+        tfvars: Vec<PathBuf>,
+        /// replace an existing profile
+        #[arg(long)]
+        force: bool,
+    },
+    /// print the path of the config file
+    Path,
+}
+
+fn main() -> ExitCode {
+    CompleteEnv::with_factory(Cli::command).complete();
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+// Function to run a command, returns false when the command found problems
+fn run(cli: &Cli) -> io::Result<bool> {
+    let config_file = config_file(cli)?;
+    let cli_rc_file = Path::new(&config_file).with_file_name(RC_NAME).to_string_lossy().to_string();
+    match &cli.command {
+        Command::List => {
+            let active = active_profile(&cli_rc_file)?;
+            let profiles = Config::load(&config_file)?.profiles();
+            if cli.json {
+                println!("{}", json!({ "active": active, "profiles": profiles }));
+            } else {
+                for profile in profiles {
+                    println!("{} {}", if profile == active { "*" } else { " " }, profile);
+                }
+            }
+        }
+        Command::Show { profile } => {
+            let mut resolver = Resolver::new();
+            resolver.config_file = config_file;
+            resolver.cli_rc_file = cli_rc_file;
+            resolver.profile = profile.clone();
+            let resolution = resolver.resolve()?;
+            if cli.json {
+                let values: Vec<serde_json::Value> = resolution
+                    .values
+                    .iter()
+                    .map(|resolved| json!({ "key": resolved.key, "value": resolved.value, "origin": resolved.origin.to_string() }))
+                    .collect();
+                println!("{}", json!({ "profile": resolution.profile, "values": values }));
+            } else {
+                println!("[{}] ({})", resolution.profile, resolution.profile_origin);
+                for resolved in &resolution.values {
+                    println!("{}={}  # {}", resolved.key, resolved.value, resolved.origin);
+                }
+            }
+        }
+        Command::Set { profile, key, value } => {
+            let mut config = Config::load(&config_file)?;
+            config.set(profile, key, value);
+            config.save(&config_file)?;
+        }
+        Command::Add { profile, user, fingerprint, key_file, tenancy, region } => {
+            let region = &lookup(region)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Unknown region {}", region)))?;
+            let mut config = match Config::load(&config_file) {
+                Ok(config) => config,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Config::default(),
+                Err(error) => return Err(error),
+            };
+            if config.profiles().contains(profile) {
+                return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("Profile {} already exists", profile)));
+            }
+            for (key, value) in [("user", user), ("fingerprint", fingerprint), ("key_file", key_file), ("tenancy", tenancy), ("region", region)] {
+                config.set(profile, key, value);
+            }
+            config.save(&config_file)?;
+        }
+        Command::Remove { profile } => {
+            let mut config = Config::load(&config_file)?;
+            if !config.remove_profile(profile) {
+                return Err(io::Error::new(io::ErrorKind::NotFound, format!("Profile {} not found", profile)));
+            }
+            config.save(&config_file)?;
+        }
+        Command::Rename { profile, new_name } => {
+            let mut config = Config::load(&config_file)?;
+            if !config.rename_profile(profile, new_name) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Profile {} can't be renamed to {}", profile, new_name),
+                ));
+            }
+            config.save(&config_file)?;
+        }
+        Command::Regions => {
+            if cli.json {
+                println!("{}", json!(identifiers()));
+            } else {
+                identifiers().iter().for_each(|region| println!("{}", region));
+            }
+        }
+        Command::Validate => {
+            let config = Config::load(&config_file)?;
+            let diagnostics = check(&config, Some(&config_file));
+            let lints = lint(&config);
+            if cli.json {
+                let diagnostics: Vec<serde_json::Value> = diagnostics
+                    .iter()
+                    .map(|d| json!({ "line": d.line, "column": d.column, "message": d.message, "help": d.help }))
+                    .collect();
+                let lints: Vec<serde_json::Value> = lints
+                    .iter()
+                    .map(|l| json!({ "rule": l.rule, "severity": l.severity.to_string(), "line": l.line, "message": l.message, "fixable": l.fixable }))
+                    .collect();
+                println!("{}", json!({ "diagnostics": diagnostics, "lints": lints }));
+            } else {
+                diagnostics.iter().for_each(|diagnostic| println!("{}\n", diagnostic));
+                lints.iter().for_each(|lint| println!("{}", lint));
+            }
+            return Ok(diagnostics.is_empty());
+        }
+        Command::Doctor { profile } => {
+            let profile = match profile {
+                Some(profile) => profile.clone(),
+                None => active_profile(&cli_rc_file)?,
+            };
+            let report = diagnose_config(&Config::load(&config_file)?, &profile);
+            if cli.json {
+                let findings: Vec<serde_json::Value> = report
+                    .findings
+                    .iter()
+                    .map(|f| json!({ "check": f.check, "severity": f.severity.to_string(), "message": f.message, "fix": f.fix }))
+                    .collect();
+                println!("{}", json!({ "profile": report.profile, "healthy": report.is_healthy(), "findings": findings }));
+            } else if report.findings.is_empty() {
+                println!("Profile {} is healthy", report.profile);
+            } else {
+                report.findings.iter().for_each(|finding| println!("{}", finding));
+            }
+            return Ok(!report.findings.iter().any(|finding| finding.severity == Severity::Error));
+        }
+        Command::Env { profile, shell } => {
+            // the profile is read from the file only, variables exported for another profile must not leak into it
+            let profile = match profile {
+                Some(profile) => profile.clone(),
+                None => active_profile(&cli_rc_file)?,
+            };
+            let resolved = Config::load(&config_file)?
+                .profile(&profile)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Profile {} not found", profile)))?;
+            print!("{}", export_env(&resolved, *shell));
+        }
+        Command::Terraform { profile, variables, tfvars } => {
+            let profile = match profile {
+                Some(profile) => profile.clone(),
+                None => active_profile(&cli_rc_file)?,
+            };
+            let resolved = Config::load(&config_file)?
+                .profile(&profile)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Profile {} not found", profile)))?;
+            if *tfvars {
+                print!("{}", terraform::tfvars(&resolved));
+            } else {
+                let style = if *variables { Provider::Variables } else { Provider::ConfigFile };
+                print!("{}\n{}", terraform::provider(&resolved, style)?, terraform::declarations(&resolved));
+            }
+        }
+        Command::Import { profile, tfvars, force } => {
+            let mut importer = Importer::new();
+            importer.config_file = config_file;
+            importer.overwrite = *force;
+            importer.read_env(|name| env::var(name).ok());
+            for path in tfvars {
+                importer.read_tfvars(&path.to_string_lossy())?;
+            }
+            let imported = importer.import(profile)?;
+            for warning in &importer.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            if cli.json {
+                println!("{}", json!({ "profile": imported.name, "config": importer.config_file }));
+            } else {
+                println!("Profile {} written to {}", imported.name, importer.config_file);
+            }
+        }
+        Command::Path => {
+            let home = UserDirs::new()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))?;
+            let path = home.home_dir().join(&config_file);
+            if cli.json {
+                println!("{}", json!({ "config": path.display().to_string() }));
+            } else {
+                println!("{}", path.display());
+            }
+        }
+    }
+    Ok(true)
+}
+
+// Function to return the config file of --config or the default config file
+fn config_file(cli: &Cli) -> io::Result<String> {
+    match &cli.config {
+        Some(path) => config_path(&path.to_string_lossy()),
+        None => default_config_file(),
+    }
+}
+
+// Function to return the config file used without --config, from OCI_CLI_CONFIG_FILE or in the user's home, like the library resolves it
+fn default_config_file() -> io::Result<String> {
+    Resolver::new().config_file_with(|name| env::var(name).ok())
+}
+
+// Function to resolve a config file given on the command line or in the environment the same way, relative paths against the working directory,
+// a leading tilde is kept relative to the user's home, which the library resolves
+fn config_path(path: &str) -> io::Result<String> {
+    match path.strip_prefix("~/") {
+        Some(path) => Ok(path.to_string()),
+        None if Path::new(path).is_absolute() => Ok(path.to_string()),
+        None => Ok(env::current_dir()?.join(path).to_string_lossy().to_string()),
+    }
+}
+
+// Function to return the config file of the command line that is completed, the completers don't receive the parsed arguments
+fn completed_config_file() -> io::Result<String> {
+    let args: Vec<String> = env::args().collect();
+    let config = args.iter().enumerate().find_map(|(index, arg)| match arg.strip_prefix("--config=") {
+        Some(path) => Some(path.to_string()),
+        None if arg == "--config" => args.get(index + 1).cloned(),
+        None => None,
+    });
+    match config {
+        Some(path) => config_path(&path),
+        None => default_config_file(),
+    }
+}
+
+// Function to complete the profile names of the config file, with their region as help.
+// Only the values of the file are read, completing never resolves a passphrase
+fn profiles() -> Vec<CompletionCandidate> {
+    let Ok(config) = completed_config_file().and_then(|config_file| Config::load(&config_file)) else {
+        return Vec::new();
+    };
+    config
+        .profiles()
+        .into_iter()
+        .map(|profile| {
+            let region = config.get(&profile, "region").or_else(|| config.get(DEFAULT_PROFILE, "region")).map(String::from);
+            CompletionCandidate::new(profile).help(region.map(Into::into))
+        })
+        .collect()
+}
+
+// Function to complete the region identifiers and codes, codes show their identifier as help
+fn regions() -> Vec<CompletionCandidate> {
+    let codes = codes().into_iter().map(|code| {
+        let identifier = lookup(&code);
+        CompletionCandidate::new(code).help(identifier.map(Into::into))
+    });
+    identifiers().into_iter().map(CompletionCandidate::new).chain(codes).collect()
+}
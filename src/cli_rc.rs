@@ -0,0 +1,175 @@
+//! The cli_rc module reads and writes the `oci_cli_rc` file of the OCI CLI, next to the config file.
+//! It holds the settings of the CLI with the default profile, command and parameter aliases, canned JMESPath queries and default parameter values per profile.
+//! The file is parsed like the config file, so comments and the layout are kept when it is written back.
+//! # Example
+//! ```rust
+//! use oci_cfg::cli_rc::CliRc;
+//!
+//! let mut rc = CliRc::parse("[OCI_CLI_SETTINGS]\ndefault_profile=ADMIN_USER\n");
+//! assert_eq!(rc.default_profile(), Some("ADMIN_USER"));
+//! rc.set_command_alias("ls", "iam compartment list");
+//! ```
+use crate::config::{Config, DEFAULT_PROFILE};
+use std::fmt;
+use std::io;
+
+/// name of the file in the config directory.
+pub static RC_NAME: &str = "oci_cli_rc";
+/// section with the settings of the CLI.
+pub static SETTINGS: &str = "OCI_CLI_SETTINGS";
+/// section mapping command aliases to commands.
+pub static COMMAND_ALIASES: &str = "OCI_CLI_COMMAND_ALIASES";
+/// section mapping parameter aliases to parameters.
+pub static PARAM_ALIASES: &str = "OCI_CLI_PARAM_ALIASES";
+/// section with named JMESPath queries.
+pub static CANNED_QUERIES: &str = "OCI_CLI_CANNED_QUERIES";
+
+// key of the settings that names the profile used without --profile
+static DEFAULT_PROFILE_KEY: &str = "default_profile";
+
+/// represents a parsed `oci_cli_rc` file.
+#[derive(Debug, Clone, Default)]
+pub struct CliRc {
+    config: Config,
+}
+
+impl CliRc {
+    /// parses the content of an `oci_cli_rc` file.
+    pub fn parse(content: &str) -> CliRc {
+        CliRc { config: Config::parse(content) }
+    }
+
+    /// reads and parses an `oci_cli_rc` file, relative paths are resolved against the user's home.
+    /// # Example
+    /// ```rust,no_run
+    /// use oci_cfg::cli_rc::CliRc;
+    /// let rc = CliRc::load(".oci/oci_cli_rc").unwrap();
+    /// ```
+    pub fn load(file_path: &str) -> io::Result<CliRc> {
+        Config::load(file_path).map(|config| CliRc { config })
+    }
+
+    /// writes the file, relative paths are resolved against the user's home.
+    pub fn save(&self, file_path: &str) -> io::Result<()> {
+        self.config.save(file_path)
+    }
+
+    /// returns the profile the CLI uses when no profile is given.
+    pub fn default_profile(&self) -> Option<&str> {
+        self.config.get(SETTINGS, DEFAULT_PROFILE_KEY)
+    }
+
+    /// returns the line of the default profile setting, starting at 1.
+    pub fn default_profile_line(&self) -> Option<usize> {
+        self.config.line(SETTINGS, DEFAULT_PROFILE_KEY)
+    }
+
+    /// sets the profile the CLI uses when no profile is given.
+    pub fn set_default_profile(&mut self, profile: &str) {
+        self.config.set(SETTINGS, DEFAULT_PROFILE_KEY, profile);
+    }
+
+    /// returns the command aliases with the commands they stand for.
+    pub fn command_aliases(&self) -> Vec<(String, String)> {
+        self.section(COMMAND_ALIASES)
+    }
+
+    /// adds or replaces a command alias.
+    pub fn set_command_alias(&mut self, alias: &str, command: &str) {
+        self.config.set(COMMAND_ALIASES, alias, command);
+    }
+
+    /// returns the parameter aliases with the parameters they stand for.
+    pub fn param_aliases(&self) -> Vec<(String, String)> {
+        self.section(PARAM_ALIASES)
+    }
+
+    /// adds or replaces a parameter alias.
+    pub fn set_param_alias(&mut self, alias: &str, parameter: &str) {
+        self.config.set(PARAM_ALIASES, alias, parameter);
+    }
+
+    /// returns the canned queries with their JMESPath expressions.
+    pub fn canned_queries(&self) -> Vec<(String, String)> {
+        self.section(CANNED_QUERIES)
+    }
+
+    /// returns the JMESPath expression of a canned query.
+    pub fn canned_query(&self, name: &str) -> Option<&str> {
+        self.config.get(CANNED_QUERIES, name)
+    }
+
+    /// adds or replaces a canned query.
+    pub fn set_canned_query(&mut self, name: &str, query: &str) {
+        self.config.set(CANNED_QUERIES, name, query);
+    }
+
+    /// returns the default parameter values of a profile, values of the profile override the values of DEFAULT.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::cli_rc::CliRc;
+    ///
+    /// let rc = CliRc::parse("[DEFAULT]\ncompartment-id=ocid1.compartment.oc1..aaaaaaaaxxxxxx\n");
+    /// assert_eq!(rc.defaults("ADMIN_USER")[0].0, "compartment-id");
+    /// ```
+    pub fn defaults(&self, profile: &str) -> Vec<(String, String)> {
+        let mut defaults = self.section(DEFAULT_PROFILE);
+        for (parameter, value) in self.section(profile) {
+            match defaults.iter_mut().find(|(name, _)| *name == parameter) {
+                Some(default) => default.1 = value,
+                None => defaults.push((parameter, value)),
+            }
+        }
+        defaults
+    }
+
+    /// sets the default value of a parameter for a profile.
+    pub fn set_default(&mut self, profile: &str, parameter: &str, value: &str) {
+        self.config.set(profile, parameter, value);
+    }
+
+    /// removes an entry from a section and returns whether it was present.
+    pub fn remove(&mut self, section: &str, key: &str) -> bool {
+        self.config.remove(section, key)
+    }
+
+    // Function to return the entries of a section in the order of the file
+    fn section(&self, name: &str) -> Vec<(String, String)> {
+        self.config
+            .keys(name)
+            .into_iter()
+            .filter_map(|key| self.config.get(name, &key).map(|value| (key.clone(), value.to_string())))
+            .collect()
+    }
+}
+
+/// makes a profile of the config file the active profile, by storing it as `default_profile`.
+/// The `oci_cli_rc` file is created when it doesn't exist, relative paths are resolved against the user's home.
+pub fn switch_profile(config_file: &str, cli_rc_file: &str, name: &str) -> io::Result<()> {
+    if !Config::load(config_file)?.profiles().iter().any(|profile| profile == name) {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("Profile {} not found", name)));
+    }
+    let mut cli_rc = match CliRc::load(cli_rc_file) {
+        Ok(cli_rc) => cli_rc,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => CliRc::default(),
+        Err(error) => return Err(error),
+    };
+    cli_rc.set_default_profile(name);
+    cli_rc.save(cli_rc_file)
+}
+
+/// returns the active profile, the `default_profile` of the `oci_cli_rc` file or DEFAULT when none is set.
+pub fn active_profile(cli_rc_file: &str) -> io::Result<String> {
+    match CliRc::load(cli_rc_file) {
+        Ok(cli_rc) => Ok(cli_rc.default_profile().unwrap_or(DEFAULT_PROFILE).to_string()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(DEFAULT_PROFILE.to_string()),
+        Err(error) => Err(error),
+    }
+}
+
+/// writes the file in its original form, including comments and blank lines.
+impl fmt::Display for CliRc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.config)
+    }
+}
@@ -0,0 +1,452 @@
+//! The config module parses an existing config file into its profiles and resolves a profile into the values used by a client.
+//! The parser keeps every line of the file, so a parsed config is written back unchanged. Values missing in a profile are inherited from the DEFAULT profile.
+//! # Example
+//! ```rust
+//! use oci_cfg::config::Config;
+//!
+//! let config = Config::parse("[DEFAULT]\nregion=us-ashburn-1\n\n[ADMIN_USER]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n");
+//! let admin = config.profile("ADMIN_USER").unwrap();
+//! assert_eq!(admin.region.as_deref(), Some("us-ashburn-1"));
+//! ```
+use crate::file;
+use crate::keys;
+use crate::passphrase::PassPhraseSource;
+use crate::secret::Secret;
+use crate::session::SessionToken;
+use directories::UserDirs;
+use rsa::RsaPrivateKey;
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// name of the profile that provides default values for all other profiles.
+pub static DEFAULT_PROFILE: &str = "DEFAULT";
+
+// keys that define the passphrase of a profile, in plain text or as reference
+static PASS_PHRASE_KEYS: [&str; 4] = ["pass_phrase", "pass_phrase_env", "pass_phrase_file", "pass_phrase_command"];
+
+// Classification of a single line in the config file
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Kind {
+    Blank,
+    Comment,
+    Section(String),
+    Entry(String, String),
+    Invalid,
+}
+
+// A line of the config file, the raw text is kept to write the file back unchanged
+#[derive(Debug, Clone)]
+pub(crate) struct Line {
+    pub(crate) raw: String,
+    pub(crate) kind: Kind,
+}
+
+impl Line {
+    fn entry(key: &str, value: &str) -> Line {
+        Line::parse(&format!("{}={}", key, value))
+    }
+
+    pub(crate) fn parse(raw: &str) -> Line {
+        let text = raw.trim_start_matches('\u{feff}').trim();
+        let kind = if text.is_empty() {
+            Kind::Blank
+        } else if text.starts_with('#') || text.starts_with(';') {
+            Kind::Comment
+        } else if text.starts_with('[') && text.ends_with(']') {
+            Kind::Section(text[1..text.len() - 1].trim().to_string())
+        } else if let Some(index) = text.find(['=', ':']) {
+            let key = text[..index].trim().to_lowercase();
+            let value = text[index + 1..].trim().to_string();
+            if key.is_empty() {
+                Kind::Invalid
+            } else {
+                Kind::Entry(key, value)
+            }
+        } else {
+            Kind::Invalid
+        };
+        Line { raw: raw.to_string(), kind }
+    }
+}
+
+/// represents a parsed config file with all of its profiles.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    lines: Vec<Line>,
+}
+
+impl Config {
+    /// parses the content of a config file. Lines that can't be parsed are kept, but ignored when resolving profiles.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::config::Config;
+    /// let config = Config::parse("[DEFAULT]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n");
+    /// ```
+    pub fn parse(content: &str) -> Config {
+        let lines = content.split('\n').map(Line::parse).collect();
+        Config { lines }
+    }
+
+    /// reads and parses a config file, relative paths are resolved against the user's home.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::config::Config;
+    /// let config = Config::load(".oci/config");
+    /// ```
+    pub fn load(file_path: &str) -> io::Result<Config> {
+        let home = UserDirs::new()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))?;
+        let content = fs::read_to_string(home.home_dir().join(file_path))?;
+        Ok(Config::parse(&content))
+    }
+
+    /// returns the names of all profiles in the order of the file.
+    pub fn profiles(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for line in &self.lines {
+            if let Kind::Section(name) = &line.kind {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        names
+    }
+
+    /// returns the value of a key as written in the given profile, without inheritance from DEFAULT.
+    /// Keys are matched case-insensitive, a repeated key overrides the earlier value.
+    pub fn get(&self, profile: &str, key: &str) -> Option<&str> {
+        let key = key.to_lowercase();
+        self.entries(profile)
+            .filter(|(name, _)| **name == key)
+            .map(|(_, value)| value.as_str())
+            .last()
+    }
+
+    /// returns the keys written in a profile in the order of the file, without inheritance from DEFAULT.
+    pub fn keys(&self, profile: &str) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for (key, _) in self.entries(profile) {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+        keys
+    }
+
+    /// returns the line number of the entry that defines a key in a profile, starting at 1. A repeated key returns the line of the last entry.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::config::Config;
+    /// let config = Config::parse("[DEFAULT]\nregion=us-ashburn-1\n");
+    /// assert_eq!(config.line("DEFAULT", "region"), Some(2));
+    /// ```
+    pub fn line(&self, profile: &str, key: &str) -> Option<usize> {
+        let key = key.to_lowercase();
+        let mut line = None;
+        let mut current: Option<&String> = None;
+        for (index, entry) in self.lines.iter().enumerate() {
+            match &entry.kind {
+                Kind::Section(name) => current = Some(name),
+                Kind::Entry(name, _) if *name == key && current.map(String::as_str) == Some(profile) => line = Some(index + 1),
+                _ => {}
+            }
+        }
+        line
+    }
+
+    /// resolves a profile, values that are missing in the profile are taken from DEFAULT.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::config::Config;
+    /// let config = Config::parse("[DEFAULT]\nregion=eu-frankfurt-1\n");
+    /// let profile = config.profile("DEFAULT").unwrap();
+    /// assert_eq!(profile.region.as_deref(), Some("eu-frankfurt-1"));
+    /// ```
+    pub fn profile(&self, name: &str) -> Option<ResolvedProfile> {
+        if !self.profiles().iter().any(|profile| profile == name) {
+            return None;
+        }
+        let value = |key: &str| {
+            self.get(name, key)
+                .or_else(|| self.get(DEFAULT_PROFILE, key))
+                .map(String::from)
+        };
+        let (pass_phrase, pass_phrase_source) = self.pass_phrase(name);
+        Some(ResolvedProfile {
+            name: name.to_string(),
+            user: value("user"),
+            fingerprint: value("fingerprint"),
+            key_file: value("key_file"),
+            tenancy: value("tenancy"),
+            region: value("region"),
+            pass_phrase,
+            pass_phrase_source,
+            security_token_file: value("security_token_file"),
+            ..Default::default()
+        })
+    }
+
+    /// sets the value of a key in a profile. Existing entries keep their position and separator,
+    /// new keys are added at the end of the profile and missing profiles are appended to the file.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::config::Config;
+    ///
+    /// let mut config = Config::parse("[DEFAULT]\nregion = us-ashburn-1\n");
+    /// config.set("DEFAULT", "region", "eu-frankfurt-1");
+    /// assert_eq!(config.to_string(), "[DEFAULT]\nregion = eu-frankfurt-1\n");
+    /// ```
+    pub fn set(&mut self, profile: &str, key: &str, value: &str) {
+        let key = key.to_lowercase();
+        let mut found = false;
+        let mut current: Option<String> = None;
+        for line in self.lines.iter_mut() {
+            match &line.kind {
+                Kind::Section(name) => current = Some(name.clone()),
+                Kind::Entry(name, _) if *name == key && current.as_deref() == Some(profile) => {
+                    line.raw = replace_value(&line.raw, value);
+                    line.kind = Kind::Entry(key.clone(), value.to_string());
+                    found = true;
+                }
+                _ => {}
+            }
+        }
+        if found {
+            return;
+        }
+        match self.insert_position(profile) {
+            Some(index) => self.lines.insert(index, Line::entry(&key, value)),
+            None => {
+                // append the profile in front of the trailing blank lines, separated by a blank line
+                let mut index = self.lines.len();
+                while index > 0 && self.lines[index - 1].kind == Kind::Blank {
+                    index -= 1;
+                }
+                let mut section = vec![Line::parse(&format!("[{}]", profile)), Line::entry(&key, value)];
+                if index > 0 {
+                    section.insert(0, Line::parse(""));
+                }
+                if index == self.lines.len() {
+                    section.push(Line::parse(""));
+                }
+                self.lines.splice(index..index, section);
+            }
+        }
+    }
+
+    /// removes a key from a profile and returns whether it was present.
+    pub fn remove(&mut self, profile: &str, key: &str) -> bool {
+        let key = key.to_lowercase();
+        let before = self.lines.len();
+        let mut current: Option<String> = None;
+        self.lines.retain(|line| match &line.kind {
+            Kind::Section(name) => {
+                current = Some(name.clone());
+                true
+            }
+            Kind::Entry(name, _) => !(*name == key && current.as_deref() == Some(profile)),
+            _ => true,
+        });
+        before != self.lines.len()
+    }
+
+    /// removes a profile with its entries and comments and returns whether it was present.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::config::Config;
+    ///
+    /// let mut config = Config::parse("[DEFAULT]\nregion=us-ashburn-1\n\n[ADMIN_USER]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n");
+    /// assert!(config.remove_profile("ADMIN_USER"));
+    /// assert_eq!(config.to_string(), "[DEFAULT]\nregion=us-ashburn-1\n");
+    /// ```
+    pub fn remove_profile(&mut self, name: &str) -> bool {
+        let before = self.lines.len();
+        let mut current = false;
+        self.lines.retain(|line| {
+            if let Kind::Section(section) = &line.kind {
+                current = section == name;
+            }
+            !current
+        });
+        let removed = before != self.lines.len();
+        // keep the line break at the end of the file
+        if removed && self.lines.last().is_some_and(|line| line.kind != Kind::Blank) {
+            self.lines.push(Line::parse(""));
+        }
+        removed
+    }
+
+    /// renames a profile and returns whether it was renamed. A profile isn't renamed onto an existing profile.
+    pub fn rename_profile(&mut self, name: &str, new_name: &str) -> bool {
+        let profiles = self.profiles();
+        if !profiles.iter().any(|profile| profile == name) || profiles.iter().any(|profile| profile == new_name) {
+            return false;
+        }
+        for line in self.lines.iter_mut() {
+            if line.kind == Kind::Section(name.to_string()) {
+                *line = Line::parse(&line.raw.replacen(name, new_name, 1));
+            }
+        }
+        true
+    }
+
+    /// stores a passphrase reference in a profile and removes a plain text passphrase and other references.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::config::Config;
+    /// use oci_cfg::passphrase::PassPhraseSource;
+    ///
+    /// let mut config = Config::parse("[DEFAULT]\npass_phrase=secret\n");
+    /// config.set_pass_phrase_source("DEFAULT", &PassPhraseSource::Env(String::from("OCI_PASSPHRASE")));
+    /// assert_eq!(config.to_string(), "[DEFAULT]\npass_phrase_env=OCI_PASSPHRASE\n");
+    /// ```
+    pub fn set_pass_phrase_source(&mut self, profile: &str, source: &PassPhraseSource) {
+        for key in PASS_PHRASE_KEYS {
+            if key != source.key() {
+                self.remove(profile, key);
+            }
+        }
+        self.set(profile, source.key(), source.value());
+    }
+
+    /// writes the config to a file, relative paths are resolved against the user's home. The file is only readable by the owner.
+    pub fn save(&self, file_path: &str) -> io::Result<()> {
+        let home = UserDirs::new()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))?;
+        file::write(&home.home_dir().join(file_path), self.to_string().as_bytes(), file::FILE_MODE)
+    }
+
+    // Function to return the lines of the file, for the lint rules
+    pub(crate) fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    // Function to return the lines of the file for the auto-fixes, which re-parse the lines they change
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Line> {
+        &mut self.lines
+    }
+
+    // Function to return the plain text passphrase or the reference of the first profile that defines one, references are resolved when the key is decoded
+    fn pass_phrase(&self, name: &str) -> (Option<Secret>, Option<PassPhraseSource>) {
+        for profile in [name, DEFAULT_PROFILE] {
+            if let Some(pass_phrase) = self.get(profile, "pass_phrase") {
+                return (Some(Secret::from(pass_phrase)), None);
+            }
+            for key in &PASS_PHRASE_KEYS[1..] {
+                if let Some(source) = self.get(profile, key).and_then(|value| PassPhraseSource::from_entry(key, value)) {
+                    return (None, Some(source));
+                }
+            }
+        }
+        (None, None)
+    }
+
+    // Function to return the index behind the last entry of a profile
+    fn insert_position(&self, profile: &str) -> Option<usize> {
+        let mut position = None;
+        let mut current = false;
+        for (index, line) in self.lines.iter().enumerate() {
+            match &line.kind {
+                Kind::Section(name) => {
+                    current = name == profile;
+                    if current {
+                        position = Some(index + 1);
+                    }
+                }
+                Kind::Entry(_, _) if current => position = Some(index + 1),
+                _ => {}
+            }
+        }
+        position
+    }
+
+    // Function to iterate over the entries of a section
+    fn entries<'a>(&'a self, profile: &str) -> impl Iterator<Item = (&'a String, &'a String)> + 'a {
+        let profile = profile.to_string();
+        let mut current: Option<&String> = None;
+        self.lines.iter().filter_map(move |line| match &line.kind {
+            Kind::Section(name) => {
+                current = Some(name);
+                None
+            }
+            Kind::Entry(key, value) if current == Some(&profile) => Some((key, value)),
+            _ => None,
+        })
+    }
+}
+
+// Function to replace the value of an entry line, keeping the key, the separator and the spacing
+fn replace_value(raw: &str, value: &str) -> String {
+    match raw.find(['=', ':']) {
+        Some(index) => {
+            let rest = &raw[index + 1..];
+            let spacing = &rest[..rest.len() - rest.trim_start().len()];
+            format!("{}{}{}", &raw[..=index], spacing, value)
+        }
+        None => raw.to_string(),
+    }
+}
+
+/// writes the config in its original form, including comments and blank lines.
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let raw: Vec<&str> = self.lines.iter().map(|line| line.raw.as_str()).collect();
+        write!(f, "{}", raw.join("\n"))
+    }
+}
+
+/// represents the effective values of a profile after DEFAULT inheritance.
+/// Credentials that don't come from a file can carry the token and the private key inline, in place of the paths.
+/// Passphrases, tokens and keys are held as secrets, so the profile can be logged without leaking them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedProfile {
+    pub name: String,
+    pub user: Option<String>,
+    pub fingerprint: Option<String>,
+    pub key_file: Option<String>,
+    pub tenancy: Option<String>,
+    pub region: Option<String>,
+    pub pass_phrase: Option<Secret>,
+    pub pass_phrase_source: Option<PassPhraseSource>,
+    pub security_token_file: Option<String>,
+    pub security_token: Option<Secret>,
+    pub private_key: Option<Secret>,
+}
+
+impl ResolvedProfile {
+    /// returns whether the profile authenticates with a security token instead of a user.
+    pub fn is_session(&self) -> bool {
+        self.security_token.is_some() || self.security_token_file.is_some()
+    }
+
+    /// reads the security token of a session profile, profiles without a token return None.
+    pub fn session_token(&self) -> io::Result<Option<SessionToken>> {
+        match (&self.security_token, &self.security_token_file) {
+            (Some(token), _) => SessionToken::parse(token.expose()).map(Some),
+            (None, Some(token_file)) => SessionToken::load(token_file).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// decodes the private key of the profile, an inline key takes precedence over the key file.
+    /// A passphrase reference is resolved here and not when the profile is loaded, so reading a profile never runs a passphrase command.
+    pub fn signing_key(&self) -> io::Result<RsaPrivateKey> {
+        let resolved = match (&self.pass_phrase, &self.pass_phrase_source) {
+            (None, Some(source)) => Some(source.resolve().map_err(|error| {
+                io::Error::new(error.kind(), format!("The passphrase of profile {} can't be resolved: {}", self.name, error))
+            })?),
+            _ => None,
+        };
+        let pass_phrase = self.pass_phrase.as_ref().or(resolved.as_ref()).map(Secret::expose);
+        match (&self.private_key, &self.key_file) {
+            (Some(pem), _) => keys::decode(pem.expose(), pass_phrase),
+            (None, Some(key_file)) => keys::load(key_file, pass_phrase),
+            (None, None) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Profile {} is missing the key key_file", self.name),
+            )),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! The convert module writes all profiles of a config as JSON, TOML or YAML and reads them back, it is built with the `serde` feature.
+//! Profiles map to objects, tables or mappings of their keys, in the order of the config file. Comments and the layout of the INI file are not converted.
+//! Passphrases are masked unless they are included explicitly, masked values are dropped when a document is read, so a mask is never written as a passphrase.
+//! # Example
+//! ```rust
+//! use oci_cfg::config::Config;
+//! use oci_cfg::convert::{export, import, Format};
+//!
+//! let config = Config::parse("[DEFAULT]\nregion=eu-frankfurt-1\npass_phrase=secret\n");
+//! let yaml = export(&config, Format::Yaml, false).unwrap();
+//! assert_eq!(yaml, "DEFAULT:\n  region: eu-frankfurt-1\n  pass_phrase: '********'\n");
+//! assert_eq!(import(&yaml, Format::Yaml).unwrap().to_string(), "[DEFAULT]\nregion=eu-frankfurt-1\n");
+//! ```
+use crate::config::Config;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// text that replaces secrets in exported documents.
+pub static MASK: &str = "********";
+
+// keys whose values are secrets
+static SECRET_KEYS: [&str; 1] = ["pass_phrase"];
+
+/// represents the formats a config is converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Json => write!(f, "json"),
+            Format::Toml => write!(f, "toml"),
+            Format::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "json" => Ok(Format::Json),
+            "toml" => Ok(Format::Toml),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            _ => Err(format!("Unknown format {}, use json, toml or yaml", name)),
+        }
+    }
+}
+
+// entries of a map in the order of the document
+struct Ordered<V>(Vec<(String, V)>);
+
+impl<V: Serialize> Serialize for Ordered<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for Ordered<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OrderedVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for OrderedVisitor<V> {
+            type Value = Ordered<V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a map")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = access.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Ordered(entries))
+            }
+        }
+
+        deserializer.deserialize_map(OrderedVisitor(PhantomData))
+    }
+}
+
+/// writes all profiles of a config in a format, passphrases are masked unless `include_secrets` is set.
+pub fn export(config: &Config, format: Format, include_secrets: bool) -> io::Result<String> {
+    let profiles = Ordered(
+        config
+            .profiles()
+            .into_iter()
+            .map(|profile| {
+                let values = config
+                    .keys(&profile)
+                    .into_iter()
+                    .filter_map(|key| {
+                        let value = config.get(&profile, &key)?;
+                        match !include_secrets && SECRET_KEYS.contains(&key.as_str()) {
+                            true => Some((key, MASK.to_string())),
+                            false => Some((key, value.to_string())),
+                        }
+                    })
+                    .collect();
+                (profile, Ordered(values))
+            })
+            .collect(),
+    );
+    let invalid = |error: String| io::Error::new(io::ErrorKind::InvalidData, error);
+    match format {
+        Format::Json => serde_json::to_string_pretty(&profiles).map(|json| json + "\n").map_err(|e| invalid(e.to_string())),
+        Format::Toml => toml::to_string(&profiles).map_err(|e| invalid(e.to_string())),
+        Format::Yaml => serde_norway::to_string(&profiles).map_err(|e| invalid(e.to_string())),
+    }
+}
+
+/// reads the profiles of a document in a format into a config. Values must be strings, masked values are dropped.
+pub fn import(content: &str, format: Format) -> io::Result<Config> {
+    let invalid = |error: String| io::Error::new(io::ErrorKind::InvalidData, error);
+    let profiles: Ordered<Ordered<String>> = match format {
+        Format::Json => serde_json::from_str(content).map_err(|e| invalid(e.to_string()))?,
+        Format::Toml => toml::from_str(content).map_err(|e| invalid(e.to_string()))?,
+        Format::Yaml => serde_norway::from_str(content).map_err(|e| invalid(e.to_string()))?,
+    };
+
+    // masks are dropped and the INI text is written by the ini format, so profiles without keys are kept as well
+    let profiles = Ordered(
+        profiles
+            .0
+            .into_iter()
+            .map(|(profile, values)| (profile, Ordered(values.0.into_iter().filter(|(_, value)| value != MASK).collect())))
+            .collect(),
+    );
+    Ok(Config::parse(&crate::ini::to_string(&profiles)?))
+}
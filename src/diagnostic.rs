@@ -0,0 +1,119 @@
+//! The diagnostic module reports malformed lines of a config file with the file name, line and column, and renders them with the offending source line.
+//! The parser doesn't stop at the first error, every line is checked, so a single run reports all problems of a hand-edited file.
+//! # Example
+//! ```rust
+//! use oci_cfg::config::Config;
+//! use oci_cfg::diagnostic::check;
+//!
+//! let diagnostics = check(&Config::parse("[DEFAULT\nregion us-ashburn-1\n"), None);
+//! assert_eq!(diagnostics.len(), 2);
+//! println!("{}", diagnostics[0]);
+//! ```
+use crate::config::{Config, Kind};
+use crate::doctor::Severity;
+use directories::UserDirs;
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// represents a problem at a span of a line. Lines and columns start at 1, the length counts characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub message: String,
+    pub source: String,
+    pub help: Option<String>,
+}
+
+/// renders the diagnostic with the source line and a marker below the span.
+/// ```text
+/// error: unclosed section header
+///  --> .oci/config:1:9
+///   |
+/// 1 | [DEFAULT
+///   |         ^
+///   = help: add ] to close the section header
+/// ```
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{}: {}", self.severity, self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, self.file.as_deref().unwrap_or("<config>"), self.line, self.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source)?;
+        write!(f, "{} | {}{}", gutter, " ".repeat(self.column - 1), "^".repeat(self.length.max(1)))?;
+        if let Some(help) = &self.help {
+            write!(f, "\n{} = help: {}", gutter, help)?;
+        }
+        Ok(())
+    }
+}
+
+/// checks every line of a config and returns the problems in the order of the file. The file name is used in the rendered location.
+pub fn check(config: &Config, file: Option<&str>) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut in_section = false;
+    for (index, line) in config.lines().iter().enumerate() {
+        let source = line.raw.trim_start_matches('\u{feff}').trim_end_matches('\r');
+        let indent = source.chars().count() - source.trim_start().chars().count();
+        let text = source.trim();
+        let mut add = |column: usize, length: usize, message: &str, help: Option<&str>| {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                file: file.map(String::from),
+                line: index + 1,
+                column: indent + column,
+                length,
+                message: message.to_string(),
+                source: source.to_string(),
+                help: help.map(String::from),
+            })
+        };
+        let width = text.chars().count();
+        match &line.kind {
+            Kind::Section(name) => {
+                in_section = true;
+                if name.is_empty() {
+                    add(1, width, "empty section name", Some("name the profile, e.g. [DEFAULT]"));
+                }
+            }
+            Kind::Entry(key, _) if !in_section => {
+                let message = format!("the key {} is defined outside of a section", key);
+                add(1, key.chars().count(), &message, Some("add a section header like [DEFAULT] above the entry"));
+            }
+            Kind::Invalid if text.starts_with('[') => match text.find(']') {
+                Some(position) => {
+                    let column = text[..=position].chars().count() + 1;
+                    add(column, width + 1 - column, "unexpected text after the section header", Some("move the text to its own line"));
+                }
+                None => add(width + 1, 1, "unclosed section header", Some("add ] to close the section header")),
+            },
+            Kind::Invalid if text.starts_with(['=', ':']) => {
+                add(1, 1, "missing key before the separator", Some("write the entry as key=value"));
+            }
+            Kind::Invalid => add(1, width, "expected a key=value entry, a [section] or a comment", Some("separate key and value with =")),
+            _ => {}
+        }
+    }
+    diagnostics
+}
+
+/// reads a config file and checks every line, relative paths are resolved against the user's home.
+/// # Example
+/// ```rust,no_run
+/// use oci_cfg::diagnostic::check_file;
+///
+/// for diagnostic in check_file(".oci/config").unwrap() {
+///     eprintln!("{}\n", diagnostic);
+/// }
+/// ```
+pub fn check_file(file_path: &str) -> io::Result<Vec<Diagnostic>> {
+    let home = UserDirs::new()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))?;
+    let content = fs::read_to_string(home.home_dir().join(file_path))?;
+    Ok(check(&Config::parse(&content), Some(file_path)))
+}
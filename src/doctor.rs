@@ -0,0 +1,291 @@
+//! The doctor module diagnoses a profile without contacting OCI.
+//! It checks the required keys, the key file with its permissions and passphrase, the fingerprint, the OCIDs and their realm, the region,
+//! the values inherited from DEFAULT and the expiry of a session token. Every finding comes with a severity and a suggested fix.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::doctor::diagnose;
+//!
+//! let report = diagnose("DEFAULT").unwrap();
+//! for finding in &report.findings {
+//!     println!("{}", finding);
+//! }
+//! ```
+use crate::config::{Config, ResolvedProfile, DEFAULT_PROFILE};
+use crate::file::{check_path, expand, PermissionIssue, FILE_MODE};
+use crate::keys::fingerprint;
+use crate::ocid::Ocid;
+use crate::region::identifiers;
+use crate::session::{Status, EXPIRY_WARNING};
+use crate::{DIR, NAME};
+use rsa::RsaPublicKey;
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// represents how severe a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// represents a single problem found in a profile, with the id of the check that found it and the key it concerns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub check: &'static str,
+    pub key: Option<&'static str>,
+    pub severity: Severity,
+    pub message: String,
+    pub fix: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} [{}]: {} ({})", self.severity, self.check, self.message, self.fix)
+    }
+}
+
+/// contains the findings of a diagnosed profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub profile: String,
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    /// returns whether the profile can be used, i.e. no finding is an error.
+    pub fn is_healthy(&self) -> bool {
+        !self.findings.iter().any(|finding| finding.severity == Severity::Error)
+    }
+
+    // Function to add a finding to the report
+    fn add(&mut self, check: &'static str, key: Option<&'static str>, severity: Severity, message: String, fix: String) {
+        self.findings.push(Finding { check, key, severity, message, fix });
+    }
+}
+
+/// diagnoses a profile of the config file in the user's home.
+pub fn diagnose(profile_name: &str) -> io::Result<Report> {
+    let config = Config::load(&format!("{}/{}", DIR, NAME))?;
+    Ok(diagnose_config(&config, profile_name))
+}
+
+/// diagnoses a profile of a parsed config.
+/// # Example
+/// ```rust
+/// use oci_cfg::config::Config;
+/// use oci_cfg::doctor::diagnose_config;
+///
+/// let config = Config::parse("[DEFAULT]\nregion=us-ashburn-1\n");
+/// let report = diagnose_config(&config, "DEFAULT");
+/// assert!(!report.is_healthy());
+/// ```
+pub fn diagnose_config(config: &Config, profile_name: &str) -> Report {
+    let mut report = Report { profile: profile_name.to_string(), findings: Vec::new() };
+    let profile = match config.profile(profile_name) {
+        Some(profile) => profile,
+        None => {
+            report.add(
+                "profile",
+                None,
+                Severity::Error,
+                format!("Profile {} does not exist", profile_name),
+                format!("add a [{}] section to the config file", profile_name),
+            );
+            return report;
+        }
+    };
+
+    check_required(config, &profile, &mut report);
+    check_inheritance(config, &profile, &mut report);
+    check_key(&profile, &mut report);
+    check_ocids(&profile, &mut report);
+    check_region(&profile, &mut report);
+    check_session(&profile, &mut report);
+    report
+}
+
+// Function to check that the keys required to sign requests are present
+fn check_required(config: &Config, profile: &ResolvedProfile, report: &mut Report) {
+    let required = if profile.is_session() {
+        vec!["fingerprint", "key_file", "tenancy", "region", "security_token_file"]
+    } else {
+        vec!["user", "fingerprint", "key_file", "tenancy", "region"]
+    };
+    for key in required {
+        if config.get(&profile.name, key).is_none() && config.get(DEFAULT_PROFILE, key).is_none() {
+            report.add(
+                "missing-key",
+                Some(key),
+                Severity::Error,
+                format!("The key {} is missing", key),
+                format!("add {}=... to [{}] or [{}]", key, profile.name, DEFAULT_PROFILE),
+            );
+        }
+    }
+}
+
+// Function to check that a key file and its fingerprint come from the same profile
+fn check_inheritance(config: &Config, profile: &ResolvedProfile, report: &mut Report) {
+    if profile.name == DEFAULT_PROFILE {
+        return;
+    }
+    let own = |key: &str| config.get(&profile.name, key).is_some();
+    let inherited = |key: &str| !own(key) && config.get(DEFAULT_PROFILE, key).is_some();
+    for (set, missing) in [("key_file", "fingerprint"), ("fingerprint", "key_file"), ("user", "tenancy")] {
+        if own(set) && inherited(missing) {
+            report.add(
+                "inheritance",
+                Some(missing),
+                Severity::Warning,
+                format!("{} is set in the profile, but {} is inherited from {}", set, missing, DEFAULT_PROFILE),
+                format!("set {} in [{}] as well", missing, profile.name),
+            );
+        }
+    }
+}
+
+// Function to check that the key file is readable, private, decrypts and matches the fingerprint
+fn check_key(profile: &ResolvedProfile, report: &mut Report) {
+    let key_file = match (&profile.private_key, &profile.key_file) {
+        (Some(_), _) => None,
+        (None, Some(key_file)) => Some(key_file),
+        (None, None) => return,
+    };
+    if let Some(key_file) = key_file {
+        let path = expand(key_file);
+        if let Err(error) = fs::read(&path) {
+            report.add(
+                "key-file",
+                Some("key_file"),
+                Severity::Error,
+                format!("The key file {} can't be read: {}", key_file, error),
+                String::from("correct key_file or generate a new key pair"),
+            );
+            return;
+        }
+        for issue in check_path(&path, FILE_MODE).unwrap_or_default() {
+            let fix = match issue {
+                PermissionIssue::Owner { .. } => format!("change the owner of {} to the current user", key_file),
+                _ => format!("run chmod 600 {}", key_file),
+            };
+            report.add("key-permissions", Some("key_file"), Severity::Warning, issue.to_string(), fix);
+        }
+    }
+
+    let key = match profile.signing_key() {
+        Ok(key) => key,
+        Err(error) => {
+            report.add(
+                "pass-phrase",
+                Some("pass_phrase"),
+                Severity::Error,
+                format!("The private key can't be decoded: {}", error),
+                String::from("check pass_phrase or the passphrase reference of the profile"),
+            );
+            return;
+        }
+    };
+    if let (Some(expected), Ok(actual)) = (&profile.fingerprint, fingerprint(&RsaPublicKey::from(&key))) {
+        if !expected.eq_ignore_ascii_case(&actual) {
+            report.add(
+                "fingerprint",
+                Some("fingerprint"),
+                Severity::Error,
+                format!("The fingerprint {} does not match the key, which has {}", expected, actual),
+                format!("set fingerprint={}", actual),
+            );
+        }
+    }
+}
+
+// Function to check the format of the OCIDs and that they belong to the same realm
+fn check_ocids(profile: &ResolvedProfile, report: &mut Report) {
+    let mut realms: Vec<(&'static str, String)> = Vec::new();
+    for (key, value, resource_type) in [("user", &profile.user, "user"), ("tenancy", &profile.tenancy, "tenancy")] {
+        if let Some(value) = value {
+            match Ocid::expect(value, resource_type) {
+                Ok(ocid) => realms.push((key, ocid.realm.to_string())),
+                Err(error) => report.add(
+                    "ocid",
+                    Some(key),
+                    Severity::Error,
+                    error.to_string(),
+                    format!("copy the {} OCID from the console", resource_type),
+                ),
+            }
+        }
+    }
+    if let [(first, realm), (second, other)] = realms.as_slice() {
+        if realm != other {
+            report.add(
+                "realm",
+                Some(*second),
+                Severity::Error,
+                format!("The {} is in realm {}, but the {} in realm {}", first, realm, second, other),
+                String::from("use a user and a tenancy of the same realm"),
+            );
+        }
+    }
+}
+
+// Function to check that the region is known to the region module
+fn check_region(profile: &ResolvedProfile, report: &mut Report) {
+    if let Some(region) = &profile.region {
+        if !identifiers().contains(region) {
+            report.add(
+                "region",
+                Some("region"),
+                Severity::Warning,
+                format!("The region {} is unknown", region),
+                String::from("use a region identifier like us-ashburn-1"),
+            );
+        }
+    }
+}
+
+// Function to check the expiry of the security token of a session profile
+fn check_session(profile: &ResolvedProfile, report: &mut Report) {
+    let token = match profile.session_token() {
+        Ok(Some(token)) => token,
+        Ok(None) => return,
+        Err(error) => {
+            report.add(
+                "session",
+                Some("security_token_file"),
+                Severity::Error,
+                format!("The security token can't be read: {}", error),
+                String::from("run oci session authenticate"),
+            );
+            return;
+        }
+    };
+    match token.status(EXPIRY_WARNING) {
+        Status::Expired => report.add(
+            "session",
+            Some("security_token_file"),
+            Severity::Error,
+            String::from("The session has expired"),
+            format!("run oci session refresh --profile {}", profile.name),
+        ),
+        Status::Expiring(remaining) => report.add(
+            "session",
+            Some("security_token_file"),
+            Severity::Warning,
+            format!("The session expires in {} seconds", remaining.as_secs()),
+            format!("run oci session refresh --profile {}", profile.name),
+        ),
+        Status::Valid(_) => {}
+    }
+}
@@ -0,0 +1,104 @@
+//! The export module prints a resolved profile as statements that set environment variables in a shell.
+//! It sets the `OCI_CLI_*` variables read by the OCI CLI and the SDKs, and the `TF_VAR_*` variables read by Terraform configurations of the OCI provider.
+//! Values are quoted for the shell, so the output can be evaluated, e.g. with `eval "$(oci-cfg env PROD)"`. Passphrases are not exported.
+//! # Example
+//! ```rust
+//! use oci_cfg::config::Config;
+//! use oci_cfg::export::{export_env, Shell};
+//!
+//! let config = Config::parse("[DEFAULT]\nregion=eu-frankfurt-1\n");
+//! let profile = config.profile("DEFAULT").unwrap();
+//! assert_eq!(export_env(&profile, Shell::Bash), "export OCI_CLI_REGION='eu-frankfurt-1'\nexport TF_VAR_region='eu-frankfurt-1'\n");
+//! ```
+use crate::auth::{ENV_FINGERPRINT, ENV_KEY_FILE, ENV_REGION, ENV_TENANCY, ENV_USER};
+use crate::config::ResolvedProfile;
+use std::fmt;
+use std::str::FromStr;
+
+/// names of the Terraform variables for the keys of a profile, as used by the OCI provider examples.
+pub static TF_VARS: [(&str, &str); 5] = [
+    ("user", "TF_VAR_user_ocid"),
+    ("fingerprint", "TF_VAR_fingerprint"),
+    ("key_file", "TF_VAR_private_key_path"),
+    ("tenancy", "TF_VAR_tenancy_ocid"),
+    ("region", "TF_VAR_region"),
+];
+
+// names of the OCI CLI variables for the keys of a profile
+static CLI_VARS: [(&str, &str); 5] = [
+    ("user", ENV_USER),
+    ("fingerprint", ENV_FINGERPRINT),
+    ("key_file", ENV_KEY_FILE),
+    ("tenancy", ENV_TENANCY),
+    ("region", ENV_REGION),
+];
+
+/// represents the shells the statements are written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Shell::Bash => write!(f, "bash"),
+            Shell::Zsh => write!(f, "zsh"),
+            Shell::Fish => write!(f, "fish"),
+            Shell::PowerShell => write!(f, "powershell"),
+        }
+    }
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "bash" | "sh" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            "powershell" | "pwsh" => Ok(Shell::PowerShell),
+            _ => Err(format!("Unknown shell {}, use bash, zsh, fish or powershell", name)),
+        }
+    }
+}
+
+/// returns one statement per line that sets the `OCI_CLI_*` variables followed by the `TF_VAR_*` variables. Keys missing in the profile are skipped.
+pub fn export_env(profile: &ResolvedProfile, shell: Shell) -> String {
+    let value = |key: &str| match key {
+        "user" => profile.user.as_deref(),
+        "fingerprint" => profile.fingerprint.as_deref(),
+        "key_file" => profile.key_file.as_deref(),
+        "tenancy" => profile.tenancy.as_deref(),
+        "region" => profile.region.as_deref(),
+        _ => None,
+    };
+    CLI_VARS
+        .iter()
+        .chain(TF_VARS.iter())
+        .filter_map(|(key, name)| value(key).map(|value| statement(shell, name, value)))
+        .collect()
+}
+
+// Function to write the statement that sets a variable, with the value quoted for the shell
+fn statement(shell: Shell, name: &str, value: &str) -> String {
+    match shell {
+        Shell::Bash | Shell::Zsh => format!("export {}='{}'\n", name, value.replace('\'', r"'\''")),
+        Shell::Fish => format!("set -gx {} '{}'\n", name, value.replace('\\', r"\\").replace('\'', r"\'")),
+        Shell::PowerShell => {
+            // PowerShell treats typographic single quotes like ', every quote is doubled
+            let quoted: String = value
+                .chars()
+                .flat_map(|c| match c {
+                    '\'' | '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}' => vec![c, c],
+                    _ => vec![c],
+                })
+                .collect();
+            format!("$env:{} = '{}'\n", name, quoted)
+        }
+    }
+}
@@ -1,6 +1,7 @@
 
 //! The file module contains helper functions to  create, read the content and check the permissions of the config file.
 //! It stores the API configuration in a file located in a hidden sub-directory of a user's home. 
+//! On Unix the directory is created with mode 0700 and the config file with mode 0600, wider permissions can be found and repaired for the directory, the config and the key files.
 //! # Example
 //! ```rust
 //! use oci_cfg::file::{create, permissions, read};
@@ -15,11 +16,20 @@
 //!    read(file_path.as_str());
 //! }
 //! ```
+use crate::config::Config;
 use directories::UserDirs;
+use std::fmt;
 use std::fs;
-use std::fs::File;
+use std::fs::{DirBuilder, File, OpenOptions};
+use std::io;
+use std::io::prelude::*;
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// mode of the config directory, only accessible by the owner.
+pub const DIR_MODE: u32 = 0o700;
+/// mode of the config and key files, only readable and writable by the owner.
+pub const FILE_MODE: u32 = 0o600;
 
 /// creates a sub-directory and the configuration file in the user's home. It returns the path of the config file as a String.
 /// # Example
@@ -45,16 +55,16 @@ pub fn create(config_dir: &str, config_file: &str) -> String {
         if home_dir_pathbuf.exists() {
             println!("{:?} already exists", home_dir_pathbuf);
         } else {
-            // Create the sub-directory.
-            match fs::create_dir_all(&home_dir_pathbuf) {
+            // Create the sub-directory, only accessible by the owner.
+            match create_dir(&home_dir_pathbuf) {
                 Err(why) => panic!("! {:?}", why.kind()),
                 Ok(_) => println!("Successfully created {:?}", home_dir_pathbuf),
             }
         }
 
-        // Create the config file in the sub-directory.
+        // Create the config file in the sub-directory, only readable by the owner.
         let file_path = home_dir_pathbuf.join(config_file);
-        match File::create(&file_path) {
+        match write(&file_path, b"", FILE_MODE) {
             Err(why) => panic!("! {:?}", why.kind()),
             Ok(_) => println!("Successfully created {:?}", file_path),
         }
@@ -161,3 +171,173 @@ pub fn read(file_path: &str) {
     }
 }
 
+
+/// expands a leading tilde in a path to the user's home directory, other paths are returned unchanged.
+/// # Example
+/// ```rust
+/// use oci_cfg::file::expand;
+///
+/// let key_file = expand("~/.oci/oci_api_key.pem");
+/// println!("{:?}", key_file);
+/// ```
+pub fn expand(file_path: &str) -> PathBuf {
+    match file_path.strip_prefix("~/") {
+        Some(relative) => match UserDirs::new() {
+            Some(user_dirs) => user_dirs.home_dir().join(relative),
+            None => PathBuf::from(file_path),
+        },
+        None => PathBuf::from(file_path),
+    }
+}
+
+/// creates a directory with its parents, on Unix only accessible by the owner.
+pub fn create_dir(path: &Path) -> io::Result<()> {
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(DIR_MODE);
+    }
+    builder.create(path)
+}
+
+/// writes a file with the given mode on Unix, replacing its content. The mode of an existing file is tightened as well.
+pub fn write(path: &Path, content: &[u8], mode: u32) -> io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(mode);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content)?;
+    set_mode(path, mode)
+}
+
+/// represents a file or directory whose permissions allow access to other users.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermissionIssue {
+    Missing(PathBuf),
+    Insecure { path: PathBuf, mode: u32, expected: u32 },
+    Owner { path: PathBuf, uid: u32 },
+}
+
+impl fmt::Display for PermissionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PermissionIssue::Missing(path) => write!(f, "{:?} does not exist", path),
+            PermissionIssue::Insecure { path, mode, expected } => {
+                write!(f, "{:?} has mode {:o}, expected {:o}", path, mode, expected)
+            }
+            PermissionIssue::Owner { path, uid } => write!(f, "{:?} is owned by another user (uid {})", path, uid),
+        }
+    }
+}
+
+/// checks the mode and the owner of the config directory, the config file and the key files of all profiles.
+/// Directories must not be accessible and files not readable by group or others. Without Unix permissions no issues are reported.
+/// # Example
+/// ```rust
+/// use oci_cfg::file::check_permissions;
+///
+/// for issue in check_permissions(".oci", "config").unwrap_or_default() {
+///     println!("{}", issue);
+/// }
+/// ```
+pub fn check_permissions(config_dir: &str, config_file: &str) -> io::Result<Vec<PermissionIssue>> {
+    let mut issues = Vec::new();
+    for (path, expected) in protected_paths(config_dir, config_file)? {
+        issues.extend(check_path(&path, expected)?);
+    }
+    Ok(issues)
+}
+
+/// tightens the mode of the config directory, the config file and the key files of all profiles, like `oci setup repair-file-permissions`.
+/// It returns the paths that were changed, files owned by other users can't be repaired and are reported by 'check_permissions'.
+/// # Example
+/// ```rust,no_run
+/// use oci_cfg::file::repair_permissions;
+///
+/// let repaired = repair_permissions(".oci", "config");
+/// ```
+pub fn repair_permissions(config_dir: &str, config_file: &str) -> io::Result<Vec<PathBuf>> {
+    let mut repaired = Vec::new();
+    for (path, expected) in protected_paths(config_dir, config_file)? {
+        let insecure = check_path(&path, expected)?
+            .iter()
+            .any(|issue| matches!(issue, PermissionIssue::Insecure { .. }));
+        if insecure {
+            set_mode(&path, expected)?;
+            println!("Repaired permissions of {:?}", path);
+            repaired.push(path);
+        }
+    }
+    Ok(repaired)
+}
+
+// Function to list the config directory, the config file and the key files with their expected mode
+fn protected_paths(config_dir: &str, config_file: &str) -> io::Result<Vec<(PathBuf, u32)>> {
+    let home = UserDirs::new()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))?;
+    let dir_path = home.home_dir().join(config_dir);
+    let file_path = dir_path.join(config_file);
+    let mut paths = vec![(dir_path, DIR_MODE), (file_path.clone(), FILE_MODE)];
+
+    if let Ok(config) = Config::load(file_path.to_str().expect("Failed to convert path to str")) {
+        for profile in config.profiles() {
+            if let Some(key_file) = config.get(&profile, "key_file") {
+                let key_path = expand(key_file);
+                if !paths.iter().any(|(path, _)| *path == key_path) {
+                    paths.push((key_path, FILE_MODE));
+                }
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// compares the mode and the owner of a single file or directory with the expected mode.
+#[cfg(unix)]
+pub fn check_path(path: &Path, expected: u32) -> io::Result<Vec<PermissionIssue>> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![PermissionIssue::Missing(path.to_path_buf())]),
+        Err(error) => return Err(error),
+    };
+    let mut issues = Vec::new();
+    let mode = metadata.mode() & 0o777;
+    if mode & 0o077 != 0 {
+        issues.push(PermissionIssue::Insecure { path: path.to_path_buf(), mode, expected });
+    }
+    // SAFETY: geteuid has no preconditions and can't fail
+    let uid = unsafe { libc::geteuid() };
+    if metadata.uid() != uid {
+        issues.push(PermissionIssue::Owner { path: path.to_path_buf(), uid: metadata.uid() });
+    }
+    Ok(issues)
+}
+
+/// compares the mode and the owner of a single file or directory with the expected mode.
+#[cfg(not(unix))]
+pub fn check_path(path: &Path, _expected: u32) -> io::Result<Vec<PermissionIssue>> {
+    if path.exists() {
+        Ok(Vec::new())
+    } else {
+        Ok(vec![PermissionIssue::Missing(path.to_path_buf())])
+    }
+}
+
+// Function to set the mode of a path, without effect on platforms without Unix permissions
+fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    }
+    #[cfg(not(unix))]
+    let _ = (path, mode);
+    Ok(())
+}
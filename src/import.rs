@@ -0,0 +1,271 @@
+//! The import module turns Terraform credentials into a profile of the config file.
+//! It reads the `TF_VAR_*` variables of the OCI provider from the environment and the variables of `*.tfvars` files, later sources override earlier ones like in Terraform.
+//! The profile is checked by the doctor before it is written, profiles with errors are rejected. The config file is written only readable by the owner.
+//! The passphrase of the key is never copied, `TF_VAR_private_key_password` in the environment is referenced with `pass_phrase_env` and a passphrase in a tfvars file is skipped with a warning.
+//! The tfvars parser reads strings, numbers and booleans, it skips lists and maps and rejects heredoc strings.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::import::Importer;
+//! use std::env;
+//!
+//! let mut importer = Importer::new();
+//! importer.read_env(|name| env::var(name).ok());
+//! importer.read_tfvars("/builds/infra/terraform.tfvars").unwrap();
+//! let profile = importer.import("CI").unwrap();
+//! println!("Profile {} imported", profile.name);
+//! ```
+use crate::config::{Config, ResolvedProfile};
+use crate::doctor::{diagnose_config, Severity};
+use crate::export::TF_VARS;
+use crate::file::create_dir;
+use crate::passphrase::PassPhraseSource;
+use crate::region::lookup;
+use crate::secret::Secret;
+use crate::{DIR, NAME};
+use directories::UserDirs;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+
+// name of the variable that holds the passphrase of the private key in the OCI provider examples
+static PASS_PHRASE_VAR: &str = "TF_VAR_private_key_password";
+
+/// collects the values of a profile from Terraform variables and writes them to the config file.
+#[derive(Debug, Clone)]
+pub struct Importer {
+    pub config_file: String,
+    /// replaces an existing profile of the same name instead of failing.
+    pub overwrite: bool,
+    /// values that were read but not imported, e.g. a passphrase in a tfvars file.
+    pub warnings: Vec<String>,
+    values: HashMap<&'static str, String>,
+    pass_phrase_source: Option<PassPhraseSource>,
+    // passphrase read through the lookup of read_env, only used by the doctor to decode the key
+    pass_phrase: Option<Secret>,
+}
+
+impl Default for Importer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Importer {
+    /// creates an importer without values that writes to the config file in the user's home.
+    pub fn new() -> Self {
+        Self {
+            config_file: format!("{}/{}", DIR, NAME),
+            overwrite: false,
+            warnings: Vec::new(),
+            values: HashMap::new(),
+            pass_phrase_source: None,
+            pass_phrase: None,
+        }
+    }
+
+    /// reads the `TF_VAR_*` variables through a lookup function, e.g. `|name| std::env::var(name).ok()`.
+    /// A passphrase in the environment is referenced by the name of its variable, its value isn't imported.
+    /// The doctor decodes the key with the passphrase of the lookup, not with the variable of the process.
+    pub fn read_env<F>(&mut self, vars: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        for (key, name) in variables() {
+            if let Some(value) = vars(name).filter(|value| !value.is_empty()) {
+                self.values.insert(key, value);
+            }
+        }
+        if let Some(pass_phrase) = vars(PASS_PHRASE_VAR).filter(|value| !value.is_empty()) {
+            self.pass_phrase_source = Some(PassPhraseSource::Env(PASS_PHRASE_VAR.to_string()));
+            self.pass_phrase = Some(Secret::new(pass_phrase));
+        }
+    }
+
+    /// reads a tfvars file, relative paths are resolved against the current directory like in Terraform.
+    pub fn read_tfvars(&mut self, file_path: &str) -> io::Result<()> {
+        let content = fs::read_to_string(env::current_dir()?.join(file_path))?;
+        self.parse_tfvars(&content)
+            .map_err(|error| io::Error::new(error.kind(), format!("{}: {}", file_path, error)))
+    }
+
+    /// reads the content of a tfvars file. Variables that aren't credentials of the provider are ignored,
+    /// a passphrase is skipped with a warning, so it isn't copied in plain text. Heredoc strings are rejected.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::import::Importer;
+    ///
+    /// let mut importer = Importer::new();
+    /// importer.parse_tfvars("region = \"eu-frankfurt-1\" # home region\ntags = {\n  team = \"infra\"\n}\n").unwrap();
+    /// assert!(importer.profile("CI").is_err());
+    /// ```
+    pub fn parse_tfvars(&mut self, content: &str) -> io::Result<()> {
+        let assignments = parse(content)?;
+        for (key, name) in variables() {
+            let name = name.trim_start_matches("TF_VAR_");
+            if let Some((_, value)) = assignments.iter().rev().find(|(variable, _)| variable == name) {
+                self.values.insert(key, value.clone());
+            }
+        }
+        let pass_phrase = PASS_PHRASE_VAR.trim_start_matches("TF_VAR_");
+        if assignments.iter().any(|(variable, _)| variable == pass_phrase) {
+            self.warnings.push(format!(
+                "{} is not imported, set {} or add a passphrase reference to the profile",
+                pass_phrase, PASS_PHRASE_VAR
+            ));
+        }
+        Ok(())
+    }
+
+    /// returns the profile of the collected values after the doctor checked it, without writing it.
+    pub fn profile(&self, name: &str) -> io::Result<ResolvedProfile> {
+        self.apply(&mut Config::default(), name)
+    }
+
+    /// writes the profile of the collected values to the config file and returns it.
+    /// An existing profile of the same name is only replaced with `overwrite`, a profile with errors is not written.
+    pub fn import(&self, name: &str) -> io::Result<ResolvedProfile> {
+        let mut config = match Config::load(&self.config_file) {
+            Ok(config) => config,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(error) => return Err(error),
+        };
+        if config.profiles().iter().any(|profile| profile == name) {
+            if !self.overwrite {
+                return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("Profile {} already exists", name)));
+            }
+            config.remove_profile(name);
+        }
+        let profile = self.apply(&mut config, name)?;
+
+        let home = UserDirs::new()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))?;
+        if let Some(parent) = home.home_dir().join(&self.config_file).parent() {
+            create_dir(parent)?;
+        }
+        config.save(&self.config_file)?;
+        Ok(profile)
+    }
+
+    // Function to add the profile to a config and check it with the doctor, region codes are converted into identifiers.
+    // The doctor checks a copy with the passphrase that was read, the config only gets the reference
+    fn apply(&self, config: &mut Config, name: &str) -> io::Result<ResolvedProfile> {
+        for (key, _) in variables() {
+            if let Some(value) = self.values.get(key) {
+                let value = match key {
+                    "region" => lookup(value).unwrap_or_else(|| value.clone()),
+                    _ => value.clone(),
+                };
+                config.set(name, key, &value);
+            }
+        }
+        let mut checked = config.clone();
+        if let Some(source) = &self.pass_phrase_source {
+            config.set_pass_phrase_source(name, source);
+        }
+        if let Some(pass_phrase) = &self.pass_phrase {
+            checked.set(name, "pass_phrase", pass_phrase.expose());
+        }
+        let errors: Vec<String> = diagnose_config(&checked, name)
+            .findings
+            .into_iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .map(|finding| finding.message)
+            .collect();
+        if !errors.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Profile {} is not valid: {}", name, errors.join("; ")),
+            ));
+        }
+        config
+            .profile(name)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Profile {} not found", name)))
+    }
+}
+
+// Function to return the keys of a profile with the Terraform variables they are imported from
+fn variables() -> impl Iterator<Item = (&'static str, &'static str)> {
+    TF_VARS.iter().copied()
+}
+
+// Function to parse the assignments of a tfvars file, lists and maps are skipped
+fn parse(content: &str) -> io::Result<Vec<(String, String)>> {
+    let mut assignments = Vec::new();
+    let mut depth = 0i32;
+    let mut comment = false;
+    for (index, line) in content.lines().enumerate() {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", index + 1, message));
+        let line = line.trim();
+        if comment {
+            comment = !line.contains("*/");
+            continue;
+        }
+        if depth > 0 {
+            depth += nesting(line);
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        if line.starts_with("/*") {
+            comment = !line.contains("*/");
+            continue;
+        }
+
+        let Some((name, value)) = line.split_once('=') else {
+            return Err(invalid("expected name = value"));
+        };
+        let (name, value) = (name.trim().trim_matches('"'), value.trim());
+        if value.starts_with("<<") {
+            return Err(invalid("heredoc strings are not supported, use a quoted string"));
+        }
+        if value.starts_with('[') || value.starts_with('{') {
+            depth = nesting(value);
+            continue;
+        }
+        if let Some(quoted) = value.strip_prefix('"') {
+            assignments.push((name.to_string(), unquote(quoted).ok_or_else(|| invalid("unterminated string"))?));
+        } else {
+            // numbers and booleans end at the first comment
+            let value = value.split(['#', '/', ' ']).next().unwrap_or_default();
+            assignments.push((name.to_string(), value.to_string()));
+        }
+    }
+    Ok(assignments)
+}
+
+// Function to return how many brackets a line opens, brackets in strings are counted as well
+fn nesting(line: &str) -> i32 {
+    line.chars()
+        .map(|c| match c {
+            '[' | '{' => 1,
+            ']' | '}' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+// Function to read a quoted HCL string up to its closing quote, the reverse of the quoting of the terraform module
+fn unquote(quoted: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = quoted.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                other => value.push(other),
+            },
+            '$' | '%' if chars.peek() == Some(&c) && chars.clone().nth(1) == Some('{') => {
+                // $${ and %%{ escape template sequences
+                chars.next();
+                value.push(c);
+            }
+            _ => value.push(c),
+        }
+    }
+    None
+}
@@ -5,7 +5,8 @@
 //! More information about the config file itself can be found in the official documentation under: <https://docs.oracle.com/en-us/iaas/Content/API/Concepts/sdkconfig.htm>
 //! # Example
 //! ```rust
-//! use oci_cfg::{profile, credentials, content};
+//! use oci_cfg::{profile, credentials_with_source, content};
+//! use oci_cfg::passphrase::PassPhraseSource;
 //! 
 //! fn main() {
 //!    profile(
@@ -15,29 +16,64 @@
 //!     "ocid1.tenancy.oc1..aaaaaaaaxxxxxx",
 //!     "IAD"
 //!    );
-//!    credentials(
+//!    credentials_with_source(
 //!     "ocid1.user.oc1..aaaaaaaaxxxxxx",
 //!     "ocid1.fingerprint.oc1..aaaaaaaaxxxxxx",
 //!     "path/to/private/key",
-//!     "passphrase"
-//!    );
+//!     &PassPhraseSource::Env(String::from("OCI_KEY_PASSPHRASE"))
+//!    ).unwrap();
 //!    content();
 //! }
 //! ```
+pub mod auth;
+pub mod cli_rc;
+pub mod config;
+#[cfg(feature = "serde")]
+pub mod convert;
+pub mod diagnostic;
+pub mod doctor;
+pub mod export;
 pub mod file;
+pub mod import;
+#[cfg(feature = "serde")]
+pub mod ini;
+pub mod keys;
+pub mod lint;
 pub mod region;
+pub mod resolve;
 pub mod log;
+pub mod ocid;
+pub mod passphrase;
+pub mod principal;
+pub mod secret;
+pub mod session;
+pub mod signer;
+pub mod terraform;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "serde")]
+pub mod typed;
+pub mod wizard;
 
+#[cfg(feature = "serde")]
+pub use ini::{from_str, profile_from_str, to_string};
+#[cfg(feature = "derive")]
+pub use oci_cfg_derive::OciProfile;
 use std::fs::OpenOptions;
 use std::io::prelude::*;
 use std::io;
 use std::path::PathBuf;
 use directories::UserDirs;
+use cli_rc::{active_profile, switch_profile, RC_NAME};
+use config::Config;
 use file::{create, permissions, read};
+use passphrase::PassPhraseSource;
 use region::identifier;
+use secret::Secret;
 
 static DIR: &str = ".oci";
 static NAME: &str = "config";
+static ADMIN: &str = "ADMIN_USER";
 
 /// represents a tenancy profile with the user's OCID, fingerprint, path to the private key, OCID of the tenancy and the region.
 #[derive(Debug)]
@@ -64,7 +100,6 @@ impl Profile {
 
         // set modification properties
         let config = OpenOptions::new()
-            .write(true)
             .append(true)
             .open(path_to_str);
         match config {
@@ -134,14 +169,14 @@ pub struct Credentials {
     user: String,
     fingerprint: String,
     key_file: String,
-    pass_phrase: String
+    pass_phrase: Secret
 }
 
 impl Credentials {
     // Function to format the Profile struct as a string
     fn admin_entry(&self) -> String {
         format!("[ADMIN_USER]\nuser: {}\nfingerprint: {}\nkey_file: {}\npass_phrase: {}\n\n", 
-        self.user, self.fingerprint, self.key_file, self.pass_phrase)
+        self.user, self.fingerprint, self.key_file, self.pass_phrase.expose())
     }
     
     // Function to write the struct to the config file
@@ -152,7 +187,6 @@ impl Credentials {
 
         // set modification properties
         let config = OpenOptions::new()
-            .write(true)
             .append(true)
             .open(path_to_str);
         match config {
@@ -172,8 +206,10 @@ impl Credentials {
 }
 
 /// adds user credentials to the config file to authenticate the user and to provide access to a defined tenancy.
+/// The passphrase is written in plain text, 'credentials_with_source' writes a reference to it instead.
 /// # Example
 /// ```rust
+/// #![allow(deprecated)]
 /// use oci_cfg::credentials;
 /// 
 /// fn main() {
@@ -185,12 +221,13 @@ impl Credentials {
 ///    );
 /// }
 /// ```
+#[deprecated(since = "0.2.0", note = "the passphrase is written in plain text, use credentials_with_source")]
 pub fn credentials(user: &str, fingerprint: &str, key_file: &str, pass_phrase: &str) {
     let admin = Credentials {
         user: String::from(user),
         fingerprint: String::from(fingerprint),
         key_file: String::from(key_file),
-        pass_phrase: String::from(pass_phrase)
+        pass_phrase: Secret::from(pass_phrase)
     };
 
     let path: String = format!("{}/{}", DIR, NAME); 
@@ -204,6 +241,50 @@ pub fn credentials(user: &str, fingerprint: &str, key_file: &str, pass_phrase: &
     }
 }
 
+/// adds user credentials to the config file with a reference to the passphrase, instead of the passphrase in plain text.
+/// The passphrase is resolved from the environment variable, the file or the command when the private key is decoded.
+/// # Example
+/// ```rust,no_run
+/// use oci_cfg::credentials_with_source;
+/// use oci_cfg::passphrase::PassPhraseSource;
+///
+/// credentials_with_source(
+///     "ocid1.user.oc1..aaaaaaaaxxxxxx",
+///     "ocid1.fingerprint.oc1..aaaaaaaaxxxxxx",
+///     "path/to/private/key",
+///     &PassPhraseSource::File(String::from("/run/secrets/oci_pass_phrase"))
+/// ).unwrap();
+/// ```
+pub fn credentials_with_source(user: &str, fingerprint: &str, key_file: &str, pass_phrase: &PassPhraseSource) -> io::Result<()> {
+    let path: String = format!("{}/{}", DIR, NAME);
+    let mut config = Config::load(path.as_str())?;
+    config.set(ADMIN, "user", user);
+    config.set(ADMIN, "fingerprint", fingerprint);
+    config.set(ADMIN, "key_file", key_file);
+    config.set_pass_phrase_source(ADMIN, pass_phrase);
+    config.save(path.as_str())?;
+    println!("Profile successfully written to {}", path.as_str());
+    Ok(())
+}
+
+/// makes a profile the active profile. The profile is stored as `default_profile` in `oci_cli_rc`,
+/// so the OCI CLI and the layered resolver use it when no profile is given.
+/// # Example
+/// ```rust,no_run
+/// use oci_cfg::{current_profile, use_profile};
+///
+/// use_profile("ADMIN_USER").unwrap();
+/// assert_eq!(current_profile().unwrap(), "ADMIN_USER");
+/// ```
+pub fn use_profile(name: &str) -> io::Result<()> {
+    switch_profile(&format!("{}/{}", DIR, NAME), &format!("{}/{}", DIR, RC_NAME), name)
+}
+
+/// returns the active profile, DEFAULT unless another profile was made active.
+pub fn current_profile() -> io::Result<String> {
+    active_profile(&format!("{}/{}", DIR, RC_NAME))
+}
+
 /// reads and returns the content of a config file as a string.
 /// # Example
 /// ```rust
@@ -0,0 +1,275 @@
+//! The lint module finds problems in a config file that the parser tolerates, but that hide mistakes or confuse other tools.
+//! Every rule has a stable id, like `duplicate-key` or `colon-separator`, so rules can be referenced in scripts and documentation.
+//! Safe problems are fixed through the config, so comments, blank lines and the layout of the file are kept.
+//! # Example
+//! ```rust
+//! use oci_cfg::config::Config;
+//! use oci_cfg::lint::{fix, lint};
+//!
+//! let mut config = Config::parse("[DEFAULT]\nregion: us-ashburn-1\nregion=eu-frankfurt-1\n");
+//! assert_eq!(lint(&config).len(), 2);
+//! fix(&mut config);
+//! assert_eq!(config.to_string(), "[DEFAULT]\nregion=eu-frankfurt-1\n");
+//! ```
+use crate::config::{Config, Kind, Line};
+use crate::doctor::Severity;
+use directories::UserDirs;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// a section is defined more than once.
+pub static DUPLICATE_SECTION: &str = "duplicate-section";
+/// a key is repeated within a section, only the last value is used.
+pub static DUPLICATE_KEY: &str = "duplicate-key";
+/// an entry separates key and value with a colon instead of an equal sign.
+pub static COLON_SEPARATOR: &str = "colon-separator";
+/// a key is not used by OCI, it is probably misspelled.
+pub static UNKNOWN_KEY: &str = "unknown-key";
+/// a line ends with whitespace.
+pub static TRAILING_WHITESPACE: &str = "trailing-whitespace";
+/// a line ends with a carriage return.
+pub static CRLF: &str = "crlf";
+/// the file starts with a byte order mark.
+pub static BOM: &str = "bom";
+/// a path points into the home directory of another user.
+pub static FOREIGN_HOME: &str = "foreign-home";
+
+// keys that are read from a profile by OCI tools
+static KNOWN_KEYS: [&str; 11] = [
+    "user",
+    "fingerprint",
+    "key_file",
+    "tenancy",
+    "region",
+    "pass_phrase",
+    "pass_phrase_env",
+    "pass_phrase_file",
+    "pass_phrase_command",
+    "security_token_file",
+    "delegation_token_file",
+];
+
+// keys that hold paths
+static PATH_KEYS: [&str; 4] = ["key_file", "security_token_file", "delegation_token_file", "pass_phrase_file"];
+
+// directories that contain the home directories of users
+static HOME_ROOTS: [&str; 3] = ["/home/", "/Users/", "C:\\Users\\"];
+
+/// represents a problem found by a rule at a line of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub line: usize,
+    pub message: String,
+    pub fixable: bool,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} [{}] line {}: {}", self.severity, self.rule, self.line, self.message)
+    }
+}
+
+/// checks a config against all rules and returns the problems in the order of the file. Line numbers start at 1.
+pub fn lint(config: &Config) -> Vec<Lint> {
+    let home = UserDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    let mut lints: Vec<Lint> = Vec::new();
+    let mut add = |rule: &'static str, severity: Severity, index: usize, message: String, fixable: bool| {
+        lints.push(Lint { rule, severity, line: index + 1, message, fixable })
+    };
+
+    let mut sections: HashMap<&str, usize> = HashMap::new();
+    let mut keys: HashMap<(&str, &str), usize> = HashMap::new();
+    let mut current = "";
+    for (index, line) in config.lines().iter().enumerate() {
+        if index == 0 && line.raw.starts_with('\u{feff}') {
+            add(BOM, Severity::Info, index, String::from("The file starts with a byte order mark"), true);
+        }
+        if line.raw.ends_with('\r') {
+            add(CRLF, Severity::Info, index, String::from("The line ends with CRLF"), true);
+        }
+        match &line.kind {
+            Kind::Section(name) => {
+                current = name;
+                if let Some(first) = sections.get(name.as_str()) {
+                    let message = format!("The section [{}] is already defined in line {}", name, first + 1);
+                    add(DUPLICATE_SECTION, Severity::Warning, index, message, true);
+                } else {
+                    sections.insert(name.as_str(), index);
+                }
+            }
+            Kind::Entry(key, value) => {
+                if let Some(first) = keys.insert((current, key.as_str()), index) {
+                    let message = format!("The key {} overrides the value in line {}", key, first + 1);
+                    add(DUPLICATE_KEY, Severity::Warning, index, message, true);
+                }
+                if separator(&line.raw) == Some(':') {
+                    let message = format!("The key {} is separated from its value by a colon", key);
+                    add(COLON_SEPARATOR, Severity::Info, index, message, true);
+                }
+                if !KNOWN_KEYS.contains(&key.as_str()) {
+                    let message = match suggestion(key) {
+                        Some(known) => format!("The key {} is unknown, did you mean {}?", key, known),
+                        None => format!("The key {} is unknown", key),
+                    };
+                    add(UNKNOWN_KEY, Severity::Warning, index, message, false);
+                }
+                let text = line.raw.trim_end_matches('\r');
+                if text.trim_end() != text {
+                    let message = format!("The value of {} ends with whitespace", key);
+                    add(TRAILING_WHITESPACE, Severity::Info, index, message, true);
+                }
+                if PATH_KEYS.contains(&key.as_str()) && is_foreign(value, home.as_deref()) {
+                    let message = format!("The path {} points into the home directory of another user", value);
+                    add(FOREIGN_HOME, Severity::Warning, index, message, false);
+                }
+            }
+            _ => {}
+        }
+    }
+    lints
+}
+
+/// applies the safe fixes to a config and returns the problems that were fixed.
+/// Unknown keys and foreign paths need a decision and are left unchanged.
+pub fn fix(config: &mut Config) -> Vec<Lint> {
+    let fixed: Vec<Lint> = lint(config).into_iter().filter(|lint| lint.fixable).collect();
+
+    // rewrite single lines: byte order mark, line endings, trailing whitespace and separators
+    for (index, line) in config.lines_mut().iter_mut().enumerate() {
+        let mut raw = line.raw.trim_end_matches('\r').to_string();
+        if index == 0 {
+            raw = raw.trim_start_matches('\u{feff}').to_string();
+        }
+        if let Kind::Entry(_, _) = line.kind {
+            raw = raw.trim_end().to_string();
+            if let Some(position) = raw.find(['=', ':']).filter(|_| separator(&raw) == Some(':')) {
+                raw.replace_range(position..=position, "=");
+            }
+        }
+        if raw != line.raw {
+            *line = Line::parse(&raw);
+        }
+    }
+
+    merge_sections(config.lines_mut());
+    remove_overridden(config.lines_mut());
+    fixed
+}
+
+// Function to move the entries of repeated sections into the first section with the same name
+fn merge_sections(lines: &mut Vec<Line>) {
+    loop {
+        let mut seen: Vec<&str> = Vec::new();
+        let mut duplicate = None;
+        for (index, line) in lines.iter().enumerate() {
+            if let Kind::Section(name) = &line.kind {
+                if seen.contains(&name.as_str()) {
+                    duplicate = Some((index, name.clone()));
+                    break;
+                }
+                seen.push(name);
+            }
+        }
+        let Some((start, name)) = duplicate else {
+            return;
+        };
+
+        // the repeated section ends at the next section, trailing blank lines stay in place
+        let mut end = start + 1;
+        while end < lines.len() && !matches!(lines[end].kind, Kind::Section(_)) {
+            end += 1;
+        }
+        while end > start + 1 && lines[end - 1].kind == Kind::Blank {
+            end -= 1;
+        }
+        let moved: Vec<Line> = lines.drain(start..end).skip(1).collect();
+
+        // insert behind the last entry of the first section
+        let mut position = 0;
+        let mut current = false;
+        for (index, line) in lines.iter().enumerate() {
+            match &line.kind {
+                Kind::Section(section) => {
+                    current = *section == name;
+                    if current {
+                        position = index + 1;
+                    }
+                }
+                Kind::Entry(_, _) | Kind::Comment if current => position = index + 1,
+                _ => {}
+            }
+        }
+        lines.splice(position..position, moved);
+    }
+}
+
+// Function to remove entries whose value is overridden by a later entry of the same section
+fn remove_overridden(lines: &mut Vec<Line>) {
+    let mut last: HashMap<(String, String), usize> = HashMap::new();
+    let mut current = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        match &line.kind {
+            Kind::Section(name) => current = name.clone(),
+            Kind::Entry(key, _) => {
+                last.insert((current.clone(), key.clone()), index);
+            }
+            _ => {}
+        }
+    }
+    let mut current = String::new();
+    let mut index = 0;
+    lines.retain(|line| {
+        let keep = match &line.kind {
+            Kind::Section(name) => {
+                current = name.clone();
+                true
+            }
+            Kind::Entry(key, _) => last.get(&(current.clone(), key.clone())) == Some(&index),
+            _ => true,
+        };
+        index += 1;
+        keep
+    });
+}
+
+// Function to return the separator of an entry line, the first equal sign or colon
+fn separator(raw: &str) -> Option<char> {
+    raw.find(['=', ':']).and_then(|index| raw[index..].chars().next())
+}
+
+// Function to return the known key that is closest to a misspelled key
+fn suggestion(key: &str) -> Option<&'static str> {
+    KNOWN_KEYS
+        .iter()
+        .map(|known| (distance(key, known), *known))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, known)| known)
+}
+
+// Function to compute the Levenshtein distance of two keys
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            row.push((previous[j] + cost).min(previous[j + 1] + 1).min(row[j] + 1));
+        }
+        previous = row;
+    }
+    previous[b.len()]
+}
+
+// Function to check whether an absolute path points into the home directory of another user
+fn is_foreign(value: &str, home: Option<&Path>) -> bool {
+    let is_home = HOME_ROOTS.iter().any(|root| value.starts_with(root));
+    match home {
+        Some(home) => is_home && !Path::new(value).starts_with(home),
+        None => false,
+    }
+}
@@ -48,4 +48,10 @@ impl Logging {
             destination: LogOutput::Stdout,
         }
     }
-}
\ No newline at end of file
+}
+
+impl Default for Logging {
+    fn default() -> Self {
+        Self::new()
+    }
+}
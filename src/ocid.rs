@@ -0,0 +1,68 @@
+//! The ocid module validates Oracle Cloud Identifiers (OCIDs) of the form `ocid1.<resource type>.<realm>.[region][.future use].<unique id>`.
+//! Parsed identifiers expose their resource type and realm, so OCIDs can be checked against the expected resource and against each other.
+//! # Example
+//! ```rust
+//! use oci_cfg::ocid::Ocid;
+//!
+//! let user = Ocid::parse("ocid1.user.oc1..aaaaaaaaxxxxxx").unwrap();
+//! assert_eq!(user.resource_type, "user");
+//! assert_eq!(user.realm, "oc1");
+//! ```
+use std::io;
+
+/// represents the parts of a well-formed OCID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ocid<'a> {
+    pub resource_type: &'a str,
+    pub realm: &'a str,
+    pub region: &'a str,
+    pub unique_id: &'a str,
+}
+
+impl<'a> Ocid<'a> {
+    /// parses an OCID and checks the format of its parts.
+    pub fn parse(value: &'a str) -> io::Result<Ocid<'a>> {
+        let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid OCID {}: {}", value, reason));
+        let parts: Vec<&str> = value.split('.').collect();
+        if parts[0] != "ocid1" {
+            return Err(invalid("expected the prefix ocid1"));
+        }
+        if parts.len() < 5 {
+            return Err(invalid("expected resource type, realm, region and unique id"));
+        }
+        let lowercase = |part: &str| part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        let ocid = Ocid {
+            resource_type: parts[1],
+            realm: parts[2],
+            region: parts[3],
+            unique_id: parts[parts.len() - 1],
+        };
+        if ocid.resource_type.is_empty() || !lowercase(ocid.resource_type) {
+            return Err(invalid("malformed resource type"));
+        }
+        if !ocid.realm.starts_with("oc") || !lowercase(ocid.realm) {
+            return Err(invalid("malformed realm"));
+        }
+        if ocid.unique_id.is_empty() || !lowercase(ocid.unique_id) {
+            return Err(invalid("malformed unique id"));
+        }
+        Ok(ocid)
+    }
+
+    /// parses an OCID and checks that it identifies a resource of the given type.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::ocid::Ocid;
+    /// assert!(Ocid::expect("ocid1.user.oc1..aaaaaaaaxxxxxx", "tenancy").is_err());
+    /// ```
+    pub fn expect(value: &'a str, resource_type: &str) -> io::Result<Ocid<'a>> {
+        let ocid = Ocid::parse(value)?;
+        if ocid.resource_type != resource_type {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid OCID {}: expected a {} but found a {}", value, resource_type, ocid.resource_type),
+            ));
+        }
+        Ok(ocid)
+    }
+}
@@ -0,0 +1,97 @@
+//! The passphrase module resolves passphrases that a profile references instead of storing them in plain text.
+//! A reference names an environment variable, a file like a mounted Docker or Kubernetes secret, or a helper command that prints the passphrase.
+//! Only the reference is written to the config file, the passphrase is resolved when the private key of the profile is decoded.
+//! # Example
+//! ```rust
+//! use oci_cfg::passphrase::PassPhraseSource;
+//!
+//! std::env::set_var("OCI_KEY_PASSPHRASE", "passphrase");
+//! let source = PassPhraseSource::Env(String::from("OCI_KEY_PASSPHRASE"));
+//! assert_eq!(source.resolve().unwrap().expose(), "passphrase");
+//! ```
+use crate::file::expand;
+use crate::secret::Secret;
+use std::env;
+use std::fs;
+use std::io;
+use std::process::Command;
+
+/// key of a profile naming the environment variable that holds the passphrase.
+pub static PASS_PHRASE_ENV: &str = "pass_phrase_env";
+/// key of a profile naming the file that holds the passphrase.
+pub static PASS_PHRASE_FILE: &str = "pass_phrase_file";
+/// key of a profile naming the command that prints the passphrase.
+pub static PASS_PHRASE_COMMAND: &str = "pass_phrase_command";
+
+/// represents a reference to a passphrase that is stored outside of the config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassPhraseSource {
+    Env(String),
+    File(String),
+    Command(String),
+}
+
+impl PassPhraseSource {
+    /// creates a source from a key and value of a profile, other keys return None.
+    pub fn from_entry(key: &str, value: &str) -> Option<PassPhraseSource> {
+        match key.to_lowercase().as_str() {
+            "pass_phrase_env" => Some(PassPhraseSource::Env(value.to_string())),
+            "pass_phrase_file" => Some(PassPhraseSource::File(value.to_string())),
+            "pass_phrase_command" => Some(PassPhraseSource::Command(value.to_string())),
+            _ => None,
+        }
+    }
+
+    /// returns the key that stores the reference in a profile.
+    pub fn key(&self) -> &'static str {
+        match self {
+            PassPhraseSource::Env(_) => PASS_PHRASE_ENV,
+            PassPhraseSource::File(_) => PASS_PHRASE_FILE,
+            PassPhraseSource::Command(_) => PASS_PHRASE_COMMAND,
+        }
+    }
+
+    /// returns the reference as it is written to the profile.
+    pub fn value(&self) -> &str {
+        match self {
+            PassPhraseSource::Env(value) | PassPhraseSource::File(value) | PassPhraseSource::Command(value) => value,
+        }
+    }
+
+    /// resolves the passphrase. Trailing line breaks of files and command output are removed.
+    pub fn resolve(&self) -> io::Result<Secret> {
+        match self {
+            PassPhraseSource::Env(name) => env::var(name).map(Secret::from).map_err(|_| {
+                io::Error::new(io::ErrorKind::NotFound, format!("Environment variable {} is not set", name))
+            }),
+            PassPhraseSource::File(path) => {
+                let content = Secret::from(fs::read_to_string(expand(path))?);
+                Ok(Secret::from(content.expose().trim_end_matches(['\r', '\n'])))
+            }
+            PassPhraseSource::Command(command) => {
+                let output = shell(command).output()?;
+                if !output.status.success() {
+                    return Err(io::Error::other(format!(
+                        "Passphrase command '{}' failed with {}",
+                        command, output.status
+                    )));
+                }
+                let stdout = Secret::from(String::from_utf8_lossy(&output.stdout).to_string());
+                Ok(Secret::from(stdout.expose().trim_end_matches(['\r', '\n'])))
+            }
+        }
+    }
+}
+
+// Function to run a command line through the shell of the platform
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
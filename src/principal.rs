@@ -0,0 +1,359 @@
+//! The principal module reads the credentials that OCI injects into workloads running on the platform, where no config file exists.
+//! OCI Functions provide a resource principal session token (RPST) and a private key through `OCI_RESOURCE_PRINCIPAL_*` environment variables.
+//! Pods in OKE use workload identity, the service account token of the pod is exchanged for a resource principal session token at the proxymux endpoint of the cluster.
+//! Compute instances use instance principals, the region, the tenancy and the leaf certificate with its key are read from the instance metadata service.
+//! All of them are converted into the same resolved profile that a config file provides, so a client runs unchanged inside and outside of OCI.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::principal::ResourcePrincipal;
+//!
+//! let principal = ResourcePrincipal::from_env().unwrap();
+//! let profile = principal.profile().unwrap();
+//! println!("Running in tenancy {:?}", profile.tenancy);
+//! ```
+use crate::auth::Transport;
+use crate::config::ResolvedProfile;
+use crate::secret::Secret;
+use crate::session::claims;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use sha1::{Digest, Sha1};
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// name of the variable holding the resource principal version.
+pub static VERSION: &str = "OCI_RESOURCE_PRINCIPAL_VERSION";
+/// name of the variable holding the resource principal session token or the path to it.
+pub static RPST: &str = "OCI_RESOURCE_PRINCIPAL_RPST";
+/// name of the variable holding the PEM encoded private key or the path to it.
+pub static PRIVATE_PEM: &str = "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM";
+/// name of the variable holding the passphrase of the private key or the path to it.
+pub static PRIVATE_PEM_PASSPHRASE: &str = "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM_PASSPHRASE";
+/// name of the variable holding the region of the resource.
+pub static REGION: &str = "OCI_RESOURCE_PRINCIPAL_REGION";
+
+/// name of the variable overriding the path to the service account token of a pod.
+pub static SERVICE_ACCOUNT_TOKEN_PATH: &str = "OCI_KUBERNETES_SERVICE_ACCOUNT_TOKEN_PATH";
+/// name of the variable overriding the path to the CA certificate of the cluster.
+pub static SERVICE_ACCOUNT_CERT_PATH: &str = "OCI_KUBERNETES_SERVICE_ACCOUNT_CERT_PATH";
+/// name of the variable holding the host of the Kubernetes API server.
+pub static SERVICE_HOST: &str = "KUBERNETES_SERVICE_HOST";
+
+/// base URL of the instance metadata service, version 2.
+pub static METADATA_URL: &str = "http://169.254.169.254/opc/v2";
+
+static DEFAULT_TOKEN_PATH: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+static DEFAULT_CERT_PATH: &str = "/var/run/secrets/kubernetes.io/serviceaccount/ca.crt";
+static PROXYMUX_PORT: u16 = 12250;
+static SUPPORTED_VERSION: &str = "2.2";
+static TENANT_PREFIX: &str = "opc-tenant:";
+const METADATA_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// represents a value that is either passed directly or referenced by an absolute path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Value(Secret),
+    File(String),
+}
+
+impl Source {
+    // Function to interpret a variable, absolute paths are read from file
+    fn from(value: String) -> Source {
+        if value.starts_with('/') {
+            Source::File(value)
+        } else {
+            Source::Value(Secret::from(value))
+        }
+    }
+
+    /// returns the value, reading it from the file when the source is a path.
+    pub fn read(&self) -> io::Result<Secret> {
+        match self {
+            Source::Value(value) => Ok(value.clone()),
+            Source::File(path) => fs::read_to_string(path).map(|value| Secret::from(value.trim())),
+        }
+    }
+}
+
+/// represents the resource principal of a function, provided as environment variables.
+#[derive(Debug, Clone)]
+pub struct ResourcePrincipal {
+    pub version: String,
+    pub region: String,
+    pub token: Source,
+    pub private_key: Source,
+    pub pass_phrase: Option<Source>,
+}
+
+impl ResourcePrincipal {
+    /// reads the resource principal from the environment of the process.
+    pub fn from_env() -> io::Result<ResourcePrincipal> {
+        ResourcePrincipal::from_vars(|name| env::var(name).ok())
+    }
+
+    /// reads the resource principal from a lookup function, in place of the environment.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::principal::ResourcePrincipal;
+    ///
+    /// let principal = ResourcePrincipal::from_vars(|name| match name {
+    ///     "OCI_RESOURCE_PRINCIPAL_VERSION" => Some(String::from("2.2")),
+    ///     "OCI_RESOURCE_PRINCIPAL_RPST" => Some(String::from("/var/run/rpst")),
+    ///     "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM" => Some(String::from("/var/run/private.pem")),
+    ///     "OCI_RESOURCE_PRINCIPAL_REGION" => Some(String::from("us-ashburn-1")),
+    ///     _ => None,
+    /// });
+    /// assert!(principal.is_ok());
+    /// ```
+    pub fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> io::Result<ResourcePrincipal> {
+        let version = required(&var, VERSION)?;
+        if version != SUPPORTED_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Resource principal version {} is not supported, expected {}", version, SUPPORTED_VERSION),
+            ));
+        }
+        Ok(ResourcePrincipal {
+            version,
+            region: required(&var, REGION)?,
+            token: Source::from(required(&var, RPST)?),
+            private_key: Source::from(required(&var, PRIVATE_PEM)?),
+            pass_phrase: var(PRIVATE_PEM_PASSPHRASE).map(Source::from),
+        })
+    }
+
+    /// converts the resource principal into a resolved profile, the tenancy is taken from the claims of the token.
+    /// Values referenced by path are kept as paths, so the profile picks up rotated files.
+    pub fn profile(&self) -> io::Result<ResolvedProfile> {
+        let token = self.token.read()?;
+        let tenancy = claims(token.expose())?
+            .get("res_tenant")
+            .and_then(|tenant| tenant.as_str())
+            .map(String::from);
+        let mut profile = ResolvedProfile {
+            name: String::from("resource_principal"),
+            tenancy,
+            region: Some(self.region.clone()),
+            pass_phrase: self.pass_phrase.as_ref().map(Source::read).transpose()?,
+            ..Default::default()
+        };
+        match &self.token {
+            Source::Value(token) => profile.security_token = Some(token.clone()),
+            Source::File(path) => profile.security_token_file = Some(path.clone()),
+        }
+        match &self.private_key {
+            Source::Value(pem) => profile.private_key = Some(pem.clone()),
+            Source::File(path) => profile.key_file = Some(path.clone()),
+        }
+        Ok(profile)
+    }
+}
+
+/// represents the workload identity of a pod in OKE.
+/// The service account token is exchanged for a resource principal session token at the proxymux endpoint of the cluster,
+/// which is reached through a transport provided by the caller, as the endpoint requires TLS with the CA certificate of the cluster.
+#[derive(Debug, Clone)]
+pub struct WorkloadIdentity {
+    pub region: String,
+    pub service_host: String,
+    pub token_file: String,
+    pub ca_cert_file: String,
+}
+
+impl WorkloadIdentity {
+    /// reads the workload identity from the environment of the process.
+    pub fn from_env() -> io::Result<WorkloadIdentity> {
+        WorkloadIdentity::from_vars(|name| env::var(name).ok())
+    }
+
+    /// reads the workload identity from a lookup function, in place of the environment.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::principal::WorkloadIdentity;
+    ///
+    /// let identity = WorkloadIdentity::from_vars(|name| match name {
+    ///     "OCI_RESOURCE_PRINCIPAL_REGION" => Some(String::from("eu-frankfurt-1")),
+    ///     "KUBERNETES_SERVICE_HOST" => Some(String::from("10.96.0.1")),
+    ///     _ => None,
+    /// })
+    /// .unwrap();
+    /// assert_eq!(identity.endpoint(), "https://10.96.0.1:12250/resourcePrincipalSessionTokens");
+    /// ```
+    pub fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> io::Result<WorkloadIdentity> {
+        Ok(WorkloadIdentity {
+            region: required(&var, REGION)?,
+            service_host: required(&var, SERVICE_HOST)?,
+            token_file: var(SERVICE_ACCOUNT_TOKEN_PATH).unwrap_or_else(|| DEFAULT_TOKEN_PATH.to_string()),
+            ca_cert_file: var(SERVICE_ACCOUNT_CERT_PATH).unwrap_or_else(|| DEFAULT_CERT_PATH.to_string()),
+        })
+    }
+
+    /// reads the service account token of the pod.
+    pub fn token(&self) -> io::Result<Secret> {
+        fs::read_to_string(&self.token_file).map(|token| Secret::from(token.trim()))
+    }
+
+    /// returns the URL of the proxymux endpoint that issues the session tokens.
+    pub fn endpoint(&self) -> String {
+        format!("https://{}:{}/resourcePrincipalSessionTokens", self.service_host, PROXYMUX_PORT)
+    }
+
+    /// exchanges the service account token for a resource principal session token and converts it into a resolved profile.
+    /// The session key is generated for the exchange and held inline, the tenancy is taken from the claims of the token.
+    pub fn profile(&self, transport: &Transport) -> io::Result<ResolvedProfile> {
+        let invalid = |error: String| io::Error::new(io::ErrorKind::InvalidData, error);
+        let session_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).map_err(|error| invalid(error.to_string()))?;
+        let public_key = RsaPublicKey::from(&session_key).to_public_key_der().map_err(|error| invalid(error.to_string()))?;
+        let body = serde_json::json!({ "podKey": STANDARD.encode(public_key.as_bytes()) }).to_string();
+        let headers = vec![
+            (String::from("authorization"), format!("Bearer {}", self.token()?.expose())),
+            (String::from("content-type"), String::from("application/json")),
+        ];
+
+        // the response is the base64 encoded JSON document that holds the token with its ST$ prefix
+        let response = transport(&self.endpoint(), &headers, &body)?;
+        let document = STANDARD.decode(response.trim()).map_err(|error| invalid(error.to_string()))?;
+        let document: serde_json::Value = serde_json::from_slice(&document).map_err(|error| invalid(error.to_string()))?;
+        let token = document["token"]
+            .as_str()
+            .ok_or_else(|| invalid(String::from("The proxymux response contains no token")))?;
+        let token = token.strip_prefix("ST$").unwrap_or(token);
+
+        let tenancy = claims(token)?.get("res_tenant").and_then(|tenant| tenant.as_str()).map(String::from);
+        let private_key = session_key.to_pkcs8_pem(LineEnding::LF).map_err(|error| invalid(error.to_string()))?;
+        Ok(ResolvedProfile {
+            name: String::from("workload_identity"),
+            tenancy,
+            region: Some(self.region.clone()),
+            security_token: Some(Secret::from(token)),
+            private_key: Some(Secret::from(private_key.as_str())),
+            ..Default::default()
+        })
+    }
+}
+
+/// represents the instance principal of a compute instance, read from the instance metadata service.
+#[derive(Debug, Clone)]
+pub struct InstancePrincipal {
+    pub region: String,
+    pub tenancy: String,
+    pub certificate: String,
+    pub intermediate_certificate: String,
+    pub private_key: Secret,
+}
+
+impl InstancePrincipal {
+    /// reads the instance principal from the metadata service of the instance.
+    pub fn from_metadata() -> io::Result<InstancePrincipal> {
+        InstancePrincipal::from_url(METADATA_URL)
+    }
+
+    /// reads the instance principal from a metadata service at the given base URL, e.g. a local stub.
+    /// # Example
+    /// ```rust,no_run
+    /// use oci_cfg::principal::InstancePrincipal;
+    ///
+    /// let principal = InstancePrincipal::from_url("http://127.0.0.1:8080/opc/v2").unwrap();
+    /// println!("Instance in tenancy {}", principal.tenancy);
+    /// ```
+    pub fn from_url(base_url: &str) -> io::Result<InstancePrincipal> {
+        let base_url = base_url.trim_end_matches('/');
+        let certificate = metadata(base_url, "identity/cert.pem")?;
+        let tenancy = tenancy(&certificate)?;
+        Ok(InstancePrincipal {
+            region: metadata(base_url, "instance/canonicalRegionName")?.trim().to_string(),
+            tenancy,
+            certificate,
+            intermediate_certificate: metadata(base_url, "identity/intermediate.pem")?,
+            private_key: Secret::from(metadata(base_url, "identity/key.pem")?),
+        })
+    }
+
+    /// returns the colon separated SHA-1 fingerprint of the leaf certificate.
+    pub fn fingerprint(&self) -> io::Result<String> {
+        let digest = Sha1::digest(pem_der(&self.certificate)?);
+        let hex: Vec<String> = digest.iter().map(|byte| format!("{:02X}", byte)).collect();
+        Ok(hex.join(":"))
+    }
+
+    /// returns the key id that identifies the leaf certificate towards the federation endpoint.
+    pub fn key_id(&self) -> io::Result<String> {
+        Ok(format!("{}/fed-x509/{}", self.tenancy, self.fingerprint()?))
+    }
+
+    /// converts the instance principal into a resolved profile with the leaf key inline.
+    pub fn profile(&self) -> io::Result<ResolvedProfile> {
+        Ok(ResolvedProfile {
+            name: String::from("instance_principal"),
+            fingerprint: Some(self.fingerprint()?),
+            tenancy: Some(self.tenancy.clone()),
+            region: Some(self.region.clone()),
+            private_key: Some(self.private_key.clone()),
+            ..Default::default()
+        })
+    }
+}
+
+// Function to request a document from the metadata service, a plain HTTP request suffices for the link-local endpoint
+fn metadata(base_url: &str, path: &str) -> io::Result<String> {
+    let url = format!("{}/{}", base_url, path);
+    let invalid = || io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid metadata URL: {}", url));
+    let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+    let (host, target) = rest.split_once('/').map(|(host, target)| (host, format!("/{}", target))).ok_or_else(invalid)?;
+    let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+    let address = address.to_socket_addrs()?.next().ok_or_else(invalid)?;
+
+    let mut stream = TcpStream::connect_timeout(&address, METADATA_TIMEOUT)?;
+    stream.set_read_timeout(Some(METADATA_TIMEOUT))?;
+    let request = format!("GET {} HTTP/1.0\r\nHost: {}\r\nAuthorization: Bearer Oracle\r\n\r\n", target, host);
+    stream.write_all(request.as_bytes())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(invalid)?;
+    let status = head.split_whitespace().nth(1).unwrap_or_default();
+    if status != "200" {
+        return Err(io::Error::other(format!("Metadata request to {} failed with status {}", url, status)));
+    }
+    Ok(body.to_string())
+}
+
+// Function to decode the DER content of a PEM document
+fn pem_der(pem: &str) -> io::Result<Vec<u8>> {
+    let content: String = pem.lines().filter(|line| !line.starts_with("-----")).collect();
+    STANDARD
+        .decode(content.trim())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))
+}
+
+// Function to read the tenancy from the subject of the leaf certificate, stored as string "opc-tenant:<ocid>"
+fn tenancy(certificate: &str) -> io::Result<String> {
+    let der = pem_der(certificate)?;
+    let missing = || io::Error::new(io::ErrorKind::InvalidData, "The certificate names no tenancy");
+    let start = der
+        .windows(TENANT_PREFIX.len())
+        .position(|window| window == TENANT_PREFIX.as_bytes())
+        .filter(|index| *index > 0)
+        .ok_or_else(missing)?;
+    // the byte in front of the value holds its length in the DER encoding, long form lengths aren't expected for an OCID
+    let length = der[start - 1];
+    if length >= 0x80 {
+        return Err(missing());
+    }
+    let value = der.get(start + TENANT_PREFIX.len()..start + length as usize).ok_or_else(missing)?;
+    Ok(String::from_utf8_lossy(value).to_string())
+}
+
+// Function to read a variable that must be set and not empty
+fn required<F: Fn(&str) -> Option<String>>(var: &F, name: &str) -> io::Result<String> {
+    match var(name) {
+        Some(value) if !value.trim().is_empty() => Ok(value.trim().to_string()),
+        _ => Err(io::Error::new(io::ErrorKind::NotFound, format!("Environment variable {} is not set", name))),
+    }
+}
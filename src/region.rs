@@ -11,6 +11,7 @@
 //! println!("The following regions can be converted with this module: {:?}", regions);
 //! ```
 #[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
 enum Codes {
     SYD,
     MEL,
@@ -52,10 +53,52 @@ enum Codes {
     SJC,
 }
 
-/// Implement the ToString trait for the Regions enum.
-impl ToString for Codes {
-    fn to_string(&self) -> String {
-        match self {
+// all region codes, used to enumerate the regions of the module
+static CODES: [Codes; 38] = [
+    Codes::SYD,
+    Codes::MEL,
+    Codes::GRU,
+    Codes::VCP,
+    Codes::YUL,
+    Codes::YYZ,
+    Codes::SCL,
+    Codes::VAP,
+    Codes::BOG,
+    Codes::CDG,
+    Codes::MRS,
+    Codes::FRA,
+    Codes::HYD,
+    Codes::BOM,
+    Codes::MTZ,
+    Codes::LIN,
+    Codes::KIX,
+    Codes::NRT,
+    Codes::QRO,
+    Codes::MTY,
+    Codes::AMS,
+    Codes::JED,
+    Codes::BEG,
+    Codes::SIN,
+    Codes::JNB,
+    Codes::ICN,
+    Codes::YNY,
+    Codes::MAD,
+    Codes::ARN,
+    Codes::ZRH,
+    Codes::AUH,
+    Codes::DXB,
+    Codes::LHR,
+    Codes::CWL,
+    Codes::IAD,
+    Codes::ORD,
+    Codes::PHX,
+    Codes::SJC,
+];
+
+/// Implement the Display trait for the Regions enum.
+impl std::fmt::Display for Codes {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let id = match self {
             Codes::IAD => String::from("us-ashburn-1"),
             Codes::LHR => String::from("uk-london-1"),
             Codes::PHX => String::from("us-phoenix-1"),
@@ -94,68 +137,119 @@ impl ToString for Codes {
             Codes::CWL => String::from("uk-cardiff-1"),
             Codes::ORD => String::from("us-chicago-1"),
             Codes::SJC => String::from("us-sanjose-1"),
-        }
+        };
+        write!(f, "{}", id)
     }
 }
 
+// region codes accepted by `identifier` and `lookup`, London keeps its historic code LON
+static KEYS: [(&str, Codes); 38] = [
+    ("iad", Codes::IAD),
+    ("lon", Codes::LHR),
+    ("phx", Codes::PHX),
+    ("fra", Codes::FRA),
+    ("syd", Codes::SYD),
+    ("mel", Codes::MEL),
+    ("gru", Codes::GRU),
+    ("vcp", Codes::VCP),
+    ("yul", Codes::YUL),
+    ("yyz", Codes::YYZ),
+    ("scl", Codes::SCL),
+    ("vap", Codes::VAP),
+    ("bog", Codes::BOG),
+    ("cdg", Codes::CDG),
+    ("mrs", Codes::MRS),
+    ("hyd", Codes::HYD),
+    ("bom", Codes::BOM),
+    ("mtz", Codes::MTZ),
+    ("lin", Codes::LIN),
+    ("kix", Codes::KIX),
+    ("nrt", Codes::NRT),
+    ("qro", Codes::QRO),
+    ("mty", Codes::MTY),
+    ("ams", Codes::AMS),
+    ("jed", Codes::JED),
+    ("beg", Codes::BEG),
+    ("sin", Codes::SIN),
+    ("jnb", Codes::JNB),
+    ("icn", Codes::ICN),
+    ("yny", Codes::YNY),
+    ("mad", Codes::MAD),
+    ("arn", Codes::ARN),
+    ("zrh", Codes::ZRH),
+    ("auh", Codes::AUH),
+    ("dxb", Codes::DXB),
+    ("cwl", Codes::CWL),
+    ("ord", Codes::ORD),
+    ("sjc", Codes::SJC),
+];
+
 /// converts a given region code to the corresponding region identifier as a string.
+/// The process exits on an unknown code, 'lookup' returns None instead.
 /// # Example
 /// ```rust
 /// use oci_cfg::region::identifier;
 /// let home = identifier("IAD");
 /// ```
 pub fn identifier(code: &str) -> String { // translate region code to string
-    // Convert input to lowercase for case-insensitivity
-    let input_lowercase = code.trim().to_lowercase();
-
-    // Match the input with the Coin enum
-    let code = match input_lowercase.as_str() {
-        "iad" => Codes::IAD,
-        "lon" => Codes::LHR,
-        "phx" => Codes::PHX,
-        "fra" => Codes::FRA,
-        "syd" => Codes::SYD,
-        "mel" => Codes::MEL,
-        "gru" => Codes::GRU,
-        "vcp" => Codes::VCP,
-        "yul" => Codes::YUL,
-        "yyz" => Codes::YYZ,
-        "scl" => Codes::SCL,
-        "vap" => Codes::VAP,
-        "bog" => Codes::BOG,
-        "cdg" => Codes::CDG,
-        "mrs" => Codes::MRS,
-        "hyd" => Codes::HYD,
-        "bom" => Codes::BOM,
-        "mtz" => Codes::MTZ,
-        "lin" => Codes::LIN,
-        "kix" => Codes::KIX,
-        "nrt" => Codes::NRT,
-        "qro" => Codes::QRO,
-        "mty" => Codes::MTY,
-        "ams" => Codes::AMS,
-        "jed" => Codes::JED,
-        "beg" => Codes::BEG,
-        "sin" => Codes::SIN,
-        "jnb" => Codes::JNB,
-        "icn" => Codes::ICN,
-        "yny" => Codes::YNY,
-        "mad" => Codes::MAD,
-        "arn" => Codes::ARN,
-        "zrh" => Codes::ZRH,
-        "auh" => Codes::AUH,
-        "dxb" => Codes::DXB,
-        "cwl" => Codes::CWL,
-        "ord" => Codes::ORD,
-        "sjc" => Codes::SJC,
-        _ => {
+    match code_identifier(code) {
+        Some(identifier) => identifier,
+        None => {
             println!("Invalid code.");
             std::process::exit(1);
         }
-    };
-    // Convert the enum variant to a string and print
-    let id = code.to_string();
-    id
+    }
+}
+
+/// returns the identifiers of all regions enabled in the module.
+/// # Example
+/// ```rust
+/// use oci_cfg::region::identifiers;
+/// assert!(identifiers().contains(&String::from("eu-frankfurt-1")));
+/// ```
+pub fn identifiers() -> Vec<String> {
+    CODES.iter().map(|code| code.to_string()).collect()
+}
+
+/// returns the codes of all regions enabled in the module.
+/// # Example
+/// ```rust
+/// use oci_cfg::region::codes;
+/// assert!(codes().contains(&String::from("FRA")));
+/// ```
+pub fn codes() -> Vec<String> {
+    KEYS.iter().map(|(key, _)| key.to_uppercase()).collect()
+}
+
+/// returns the identifier of a region code or identifier, unknown values return None. Codes are the ones 'identifier' accepts.
+/// # Example
+/// ```rust
+/// use oci_cfg::region::lookup;
+/// assert_eq!(lookup("fra").as_deref(), Some("eu-frankfurt-1"));
+/// assert_eq!(lookup("LON").as_deref(), Some("uk-london-1"));
+/// assert_eq!(lookup("eu-frankfurt-1").as_deref(), Some("eu-frankfurt-1"));
+/// assert_eq!(lookup("XYZ"), None);
+/// ```
+pub fn lookup(region: &str) -> Option<String> {
+    let region = region.trim();
+    code_identifier(region).or_else(|| identifiers().into_iter().find(|identifier| identifier == region))
+}
+
+// Function to translate a region code into its identifier, case-insensitive
+fn code_identifier(code: &str) -> Option<String> {
+    let code = code.trim().to_lowercase();
+    KEYS.iter().find(|(key, _)| *key == code).map(|(_, region)| region.to_string())
+}
+
+/// returns the region identifiers that start with the given prefix, to complete a partial input.
+/// # Example
+/// ```rust
+/// use oci_cfg::region::complete;
+/// assert_eq!(complete("eu-fr"), vec![String::from("eu-frankfurt-1")]);
+/// ```
+pub fn complete(prefix: &str) -> Vec<String> {
+    let prefix = prefix.trim().to_lowercase();
+    identifiers().into_iter().filter(|region| region.starts_with(&prefix)).collect()
 }
 
 /// lists all regions enabled in the module.
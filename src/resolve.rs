@@ -0,0 +1,282 @@
+//! The resolve module resolves a profile in layers, the way the OCI CLI does: explicit values, the `OCI_CLI_*` environment variables,
+//! the selected profile and DEFAULT, in this order. Without explicit profile or `OCI_CLI_PROFILE`, the `default_profile` of `oci_cli_rc` selects the profile.
+//! Without an explicit config file, `OCI_CLI_CONFIG_FILE` selects the config file like in the OCI CLI.
+//! Every effective value records where it came from, the layer with the variable or the file and line, which answers questions like "why is it using Frankfurt?".
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::resolve::Resolver;
+//!
+//! let resolution = Resolver::new().resolve().unwrap();
+//! if let Some(origin) = resolution.origin("region") {
+//!     println!("region {} comes from {}", resolution.get("region").unwrap(), origin);
+//! }
+//! ```
+use crate::auth::{ENV_FINGERPRINT, ENV_KEY_FILE, ENV_REGION, ENV_TENANCY, ENV_USER};
+use crate::cli_rc::{CliRc, RC_NAME};
+use crate::config::{Config, ResolvedProfile, DEFAULT_PROFILE};
+use crate::{DIR, NAME};
+use std::env;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// name of the variable pointing to a config file in place of the file in the user's home.
+pub static ENV_CONFIG_FILE: &str = "OCI_CLI_CONFIG_FILE";
+/// name of the variable selecting the profile of the config file.
+pub static ENV_PROFILE: &str = "OCI_CLI_PROFILE";
+
+// keys that can be overridden by environment variables, with the name of the variable
+static ENV_KEYS: [(&str, &str); 5] = [
+    ("user", ENV_USER),
+    ("fingerprint", ENV_FINGERPRINT),
+    ("key_file", ENV_KEY_FILE),
+    ("tenancy", ENV_TENANCY),
+    ("region", ENV_REGION),
+];
+
+// keys whose values are secrets and are not listed in a resolution
+static HIDDEN_KEYS: [&str; 1] = ["pass_phrase"];
+
+/// represents the layers of the resolution, from the highest to the lowest precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Explicit,
+    Environment,
+    Settings,
+    Profile,
+    Default,
+}
+
+/// represents where a value came from. Environment values name the variable, profile values the file and line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin {
+    pub layer: Layer,
+    pub source: String,
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.layer, self.line) {
+            (Layer::Explicit, _) => write!(f, "explicit value"),
+            (Layer::Environment, _) => write!(f, "environment variable {}", self.source),
+            (Layer::Settings, Some(line)) => write!(f, "default_profile at {}:{}", self.source, line),
+            (Layer::Profile, Some(line)) => write!(f, "profile at {}:{}", self.source, line),
+            (Layer::Default, Some(line)) => write!(f, "{} profile at {}:{}", DEFAULT_PROFILE, self.source, line),
+            (_, None) => write!(f, "{}", self.source),
+        }
+    }
+}
+
+/// represents an effective value with its origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub key: String,
+    pub value: String,
+    pub origin: Origin,
+}
+
+/// contains the effective values of the selected profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolution {
+    pub profile: String,
+    pub profile_origin: Origin,
+    pub values: Vec<Resolved>,
+    base: ResolvedProfile,
+}
+
+impl Resolution {
+    /// returns the effective value of a key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.resolved(key).map(|resolved| resolved.value.as_str())
+    }
+
+    /// returns where the effective value of a key came from.
+    pub fn origin(&self, key: &str) -> Option<&Origin> {
+        self.resolved(key).map(|resolved| &resolved.origin)
+    }
+
+    /// returns the effective profile, passphrases and tokens are taken from the config file.
+    pub fn profile(&self) -> ResolvedProfile {
+        let value = |key: &str| self.get(key).map(String::from);
+        ResolvedProfile {
+            name: self.profile.clone(),
+            user: value("user"),
+            fingerprint: value("fingerprint"),
+            key_file: value("key_file"),
+            tenancy: value("tenancy"),
+            region: value("region"),
+            security_token_file: value("security_token_file"),
+            ..self.base.clone()
+        }
+    }
+
+    // Function to return the resolved entry of a key
+    fn resolved(&self, key: &str) -> Option<&Resolved> {
+        let key = key.to_lowercase();
+        self.values.iter().find(|resolved| resolved.key == key)
+    }
+}
+
+/// contains the inputs of the layered resolution.
+/// # Example
+/// ```rust
+/// use oci_cfg::config::Config;
+/// use oci_cfg::resolve::{Layer, Resolver};
+///
+/// let config = Config::parse("[DEFAULT]\nregion=eu-frankfurt-1\n");
+/// let mut resolver = Resolver::new();
+/// resolver.set("user", "ocid1.user.oc1..aaaaaaaaxxxxxx");
+/// let resolution = resolver.resolve_config(&config, |_| None).unwrap();
+/// assert_eq!(resolution.origin("user").unwrap().layer, Layer::Explicit);
+/// assert_eq!(resolution.origin("region").unwrap().line, Some(2));
+/// ```
+pub struct Resolver {
+    pub config_file: String,
+    pub cli_rc_file: String,
+    pub profile: Option<String>,
+    pub explicit: Vec<(String, String)>,
+    pub cli_rc: Option<CliRc>,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self {
+            config_file: format!("{}/{}", DIR, NAME),
+            cli_rc_file: format!("{}/{}", DIR, RC_NAME),
+            profile: None,
+            explicit: Vec::new(),
+            cli_rc: None,
+        }
+    }
+
+    /// sets an explicit value, it takes precedence over all other layers.
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.explicit.push((key.to_lowercase(), value.to_string()));
+    }
+
+    /// returns the config file to load, the config file of the resolver when it was changed, otherwise `OCI_CLI_CONFIG_FILE` of the given variable lookup
+    /// or the file in the user's home. A relative path in the variable is resolved against the current directory, a leading tilde against the user's home.
+    /// # Example
+    /// ```rust
+    /// use oci_cfg::resolve::Resolver;
+    ///
+    /// let resolver = Resolver::new();
+    /// let config_file = resolver.config_file_with(|_| Some(String::from("/etc/oci/config"))).unwrap();
+    /// assert_eq!(config_file, "/etc/oci/config");
+    /// ```
+    pub fn config_file_with<F>(&self, vars: F) -> io::Result<String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if self.config_file != format!("{}/{}", DIR, NAME) {
+            return Ok(self.config_file.clone());
+        }
+        match vars(ENV_CONFIG_FILE).filter(|path| !path.is_empty()) {
+            Some(path) => match path.strip_prefix("~/") {
+                Some(path) => Ok(path.to_string()),
+                None if Path::new(&path).is_absolute() => Ok(path),
+                None => Ok(env::current_dir()?.join(path).to_string_lossy().to_string()),
+            },
+            None => Ok(self.config_file.clone()),
+        }
+    }
+
+    /// resolves the profile from the config file, the `oci_cli_rc` file and the process environment. Missing files count as empty.
+    pub fn resolve(&self) -> io::Result<Resolution> {
+        let config_file = self.config_file_with(|name| env::var(name).ok())?;
+        let config = match Config::load(&config_file) {
+            Ok(config) => config,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(error) => return Err(error),
+        };
+        let cli_rc = match (&self.cli_rc, CliRc::load(&self.cli_rc_file)) {
+            (Some(_), _) => None,
+            (None, Ok(cli_rc)) => Some(cli_rc),
+            (None, Err(error)) if error.kind() == io::ErrorKind::NotFound => None,
+            (None, Err(error)) => return Err(error),
+        };
+        self.resolve_with(&config_file, &config, cli_rc.as_ref().or(self.cli_rc.as_ref()), |name| env::var(name).ok())
+    }
+
+    /// resolves the profile from a parsed config, the parsed `oci_cli_rc` of the resolver and the given variable lookup.
+    /// The profile is selected explicitly, by `OCI_CLI_PROFILE`, by the `default_profile` setting or defaults to DEFAULT.
+    /// A selected profile that doesn't exist is an error.
+    pub fn resolve_config<F>(&self, config: &Config, vars: F) -> io::Result<Resolution>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        self.resolve_with(&self.config_file, config, self.cli_rc.as_ref(), vars)
+    }
+
+    // Function to resolve the profile with the given settings
+    fn resolve_with<F>(&self, config_file: &str, config: &Config, cli_rc: Option<&CliRc>, vars: F) -> io::Result<Resolution>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let vars = |name: &str| vars(name).filter(|value| !value.is_empty());
+        let setting = cli_rc.and_then(|cli_rc| cli_rc.default_profile().map(|profile| (profile, cli_rc.default_profile_line())));
+        let (profile, profile_origin) = match (&self.profile, vars(ENV_PROFILE), setting) {
+            (Some(profile), _, _) => (profile.clone(), self.origin(Layer::Explicit, "")),
+            (None, Some(profile), _) => (profile, self.origin(Layer::Environment, ENV_PROFILE)),
+            (None, None, Some((profile, line))) => {
+                (profile.to_string(), Origin { layer: Layer::Settings, source: self.cli_rc_file.clone(), line })
+            }
+            (None, None, None) => (DEFAULT_PROFILE.to_string(), self.origin(Layer::Default, DEFAULT_PROFILE)),
+        };
+        let exists = config.profiles().contains(&profile);
+        if !exists && profile_origin.layer != Layer::Default {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Profile {} selected by {} not found", profile, profile_origin),
+            ));
+        }
+
+        // keys in the order of the environment variables, followed by the keys of the files
+        let mut keys: Vec<String> = ENV_KEYS.iter().map(|(key, _)| key.to_string()).collect();
+        for key in self.explicit.iter().map(|(key, _)| key.clone()).chain(config.keys(&profile)).chain(config.keys(DEFAULT_PROFILE)) {
+            if !keys.contains(&key) && !HIDDEN_KEYS.contains(&key.as_str()) {
+                keys.push(key);
+            }
+        }
+
+        let mut values: Vec<Resolved> = Vec::new();
+        for key in keys {
+            let variable = ENV_KEYS.iter().find(|(name, _)| *name == key).map(|(_, variable)| *variable);
+            let explicit = self.explicit.iter().rev().find(|(name, _)| *name == key);
+            let resolved = if let Some((_, value)) = explicit {
+                Some((value.clone(), self.origin(Layer::Explicit, "")))
+            } else if let Some(value) = variable.and_then(&vars) {
+                Some((value, self.origin(Layer::Environment, variable.unwrap_or_default())))
+            } else if let Some(value) = config.get(&profile, &key) {
+                Some((value.to_string(), self.line(config_file, Layer::Profile, config.line(&profile, &key))))
+            } else {
+                config
+                    .get(DEFAULT_PROFILE, &key)
+                    .map(|value| (value.to_string(), self.line(config_file, Layer::Default, config.line(DEFAULT_PROFILE, &key))))
+            };
+            if let Some((value, origin)) = resolved {
+                values.push(Resolved { key, value, origin });
+            }
+        }
+
+        let base = config.profile(&profile).unwrap_or_else(|| ResolvedProfile { name: profile.clone(), ..Default::default() });
+        Ok(Resolution { profile, profile_origin, values, base })
+    }
+
+    // Function to create the origin of a value without line
+    fn origin(&self, layer: Layer, source: &str) -> Origin {
+        Origin { layer, source: source.to_string(), line: None }
+    }
+
+    // Function to create the origin of a value read from the config file
+    fn line(&self, config_file: &str, layer: Layer, line: Option<usize>) -> Origin {
+        Origin { layer, source: config_file.to_string(), line }
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,76 @@
+//! The secret module wraps sensitive values like passphrases, private keys and session tokens.
+//! A secret is redacted when it is formatted for debugging or display, and its memory is zeroized when it is dropped.
+//! # Example
+//! ```rust
+//! use oci_cfg::secret::Secret;
+//!
+//! let pass_phrase = Secret::from("passphrase");
+//! assert_eq!(format!("{:?}", pass_phrase), "Secret(********)");
+//! assert_eq!(pass_phrase.expose(), "passphrase");
+//! ```
+use std::fmt;
+use zeroize::Zeroize;
+
+static REDACTED: &str = "********";
+
+/// holds a sensitive value that is redacted in output and zeroized on drop.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    /// creates a secret from a value.
+    pub fn new(value: String) -> Secret {
+        Secret(value)
+    }
+
+    /// returns the value, the caller is responsible to not leak it.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// appends a character, the previous buffer is zeroized when the secret grows.
+    pub fn push(&mut self, c: char) {
+        if self.0.len() + c.len_utf8() > self.0.capacity() {
+            let mut value = String::with_capacity((self.0.capacity() * 2).max(self.0.len() + c.len_utf8()).max(16));
+            value.push_str(&self.0);
+            self.0.zeroize();
+            self.0 = value;
+        }
+        self.0.push(c);
+    }
+
+    /// removes the last character, the buffer is zeroized when the secret is dropped.
+    pub fn pop(&mut self) -> Option<char> {
+        self.0.pop()
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Secret {
+        Secret(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Secret {
+        Secret(value.to_string())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret({})", REDACTED)
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", REDACTED)
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
@@ -0,0 +1,111 @@
+//! The session module reads the security tokens of profiles created by `oci session authenticate`.
+//! Session profiles reference a `security_token_file` instead of a user and sign requests with a key id of the form `ST$<token>`.
+//! The token is a JWT, its expiry is decoded to report the remaining time and to flag profiles that are expired or about to expire.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::session::SessionToken;
+//!
+//! let token = SessionToken::load("~/.oci/sessions/DEFAULT/token").unwrap();
+//! println!("The session expires in {:?}", token.remaining());
+//! ```
+use crate::file::expand;
+use crate::secret::Secret;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use std::fs;
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// remaining lifetime below which a session is flagged as expiring.
+pub const EXPIRY_WARNING: Duration = Duration::from_secs(10 * 60);
+
+/// represents the state of a session token at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Valid(Duration),
+    Expiring(Duration),
+    Expired,
+}
+
+/// represents a security token with its decoded expiry.
+#[derive(Debug, Clone)]
+pub struct SessionToken {
+    token: Secret,
+    expires: SystemTime,
+}
+
+impl SessionToken {
+    /// decodes the claims of a token and returns the token with its expiry.
+    pub fn parse(token: &str) -> io::Result<SessionToken> {
+        let token = token.trim();
+        let claims = claims(token)?;
+        let exp = claims
+            .get("exp")
+            .and_then(|exp| exp.as_u64())
+            .ok_or_else(|| invalid("The security token has no expiry"))?;
+        Ok(SessionToken {
+            token: Secret::from(token),
+            expires: UNIX_EPOCH + Duration::from_secs(exp),
+        })
+    }
+
+    /// reads a token file, a leading tilde in the path is expanded to the user's home.
+    pub fn load(token_file: &str) -> io::Result<SessionToken> {
+        let token = fs::read_to_string(expand(token_file))?;
+        SessionToken::parse(&token)
+    }
+
+    /// returns the raw token.
+    pub fn token(&self) -> &str {
+        self.token.expose()
+    }
+
+    /// returns the key id used to sign requests with the token.
+    pub fn key_id(&self) -> String {
+        format!("ST${}", self.token.expose())
+    }
+
+    /// returns the point in time the token expires.
+    pub fn expires(&self) -> SystemTime {
+        self.expires
+    }
+
+    /// returns the remaining lifetime of the token, or None when it has expired.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expires.duration_since(SystemTime::now()).ok()
+    }
+
+    /// returns whether the token has expired.
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_none()
+    }
+
+    /// classifies the token, tokens that expire within the given threshold are flagged as expiring.
+    pub fn status(&self, threshold: Duration) -> Status {
+        match self.remaining() {
+            None => Status::Expired,
+            Some(remaining) if remaining < threshold => Status::Expiring(remaining),
+            Some(remaining) => Status::Valid(remaining),
+        }
+    }
+}
+
+/// decodes the claims of a JWT without verifying its signature.
+pub fn claims(token: &str) -> io::Result<serde_json::Map<String, serde_json::Value>> {
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| invalid("The security token is not a JWT"))?;
+    let json = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|error| invalid(&error.to_string()))?;
+    match serde_json::from_slice(&json) {
+        Ok(serde_json::Value::Object(claims)) => Ok(claims),
+        _ => Err(invalid("The claims of the security token can't be decoded")),
+    }
+}
+
+// Function to create an error for malformed tokens
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
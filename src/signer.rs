@@ -0,0 +1,147 @@
+//! The signer module signs HTTP requests with the API key of a resolved profile, following the signature version 1 required by the OCI API.
+//! The signature covers the date, the request target and the host of a request. Requests with a body also sign the content length, the content type and the SHA-256 hash of the body.
+//! The module doesn't depend on an HTTP client, headers are handled as a list of name and value pairs that is passed to the client of choice.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::config::Config;
+//! use oci_cfg::signer::Signer;
+//!
+//! let config = Config::load(".oci/config").unwrap();
+//! let signer = Signer::from_profile(&config.profile("DEFAULT").unwrap()).unwrap();
+//! let mut headers: Vec<(String, String)> = Vec::new();
+//! signer.sign("GET", "https://iaas.us-ashburn-1.oraclecloud.com/20160918/vcns", &mut headers, b"").unwrap();
+//! ```
+use crate::config::ResolvedProfile;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rsa::pkcs1v15::SigningKey;
+use rsa::signature::{SignatureEncoding, Signer as _};
+use rsa::RsaPrivateKey;
+use sha2::{Digest, Sha256};
+use std::io;
+use std::time::SystemTime;
+
+// headers signed for every request
+static GENERIC_HEADERS: [&str; 3] = ["date", "(request-target)", "host"];
+// headers signed in addition for requests with a body
+static BODY_HEADERS: [&str; 3] = ["content-length", "content-type", "x-content-sha256"];
+
+/// signs HTTP requests with a private key on behalf of the identity named in the key id.
+pub struct Signer {
+    key_id: String,
+    signing_key: SigningKey<Sha256>,
+}
+
+impl Signer {
+    /// creates a signer from a key id and the private key that belongs to it.
+    /// For API keys the key id is composed of the tenancy, the user and the fingerprint, separated by slashes.
+    pub fn new(key_id: &str, private_key: RsaPrivateKey) -> Signer {
+        Signer {
+            key_id: key_id.to_string(),
+            signing_key: SigningKey::<Sha256>::new(private_key),
+        }
+    }
+
+    /// creates a signer from the tenancy, user, fingerprint, key file and passphrase of a resolved profile.
+    /// Session profiles and resource principals sign with their security token instead, an expired token is rejected.
+    pub fn from_profile(profile: &ResolvedProfile) -> io::Result<Signer> {
+        if let Some(token) = profile.session_token()? {
+            if token.is_expired() {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("The session of profile {} has expired, run 'oci session refresh'", profile.name),
+                ));
+            }
+            return Ok(Signer::new(&token.key_id(), profile.signing_key()?));
+        }
+        let tenancy = required(&profile.tenancy, "tenancy", &profile.name)?;
+        let user = required(&profile.user, "user", &profile.name)?;
+        let fingerprint = required(&profile.fingerprint, "fingerprint", &profile.name)?;
+        Ok(Signer::new(&format!("{}/{}/{}", tenancy, user, fingerprint), profile.signing_key()?))
+    }
+
+    /// returns the key id that is sent with every signature.
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// signs a request and adds the authorization header to the given headers.
+    /// Missing date and host headers are added, for POST, PUT and PATCH requests the body headers are added as well.
+    /// Header names are matched case-insensitive, existing values are signed as they are.
+    pub fn sign(&self, method: &str, url: &str, headers: &mut Vec<(String, String)>, body: &[u8]) -> io::Result<()> {
+        let method = method.to_lowercase();
+        let (host, target) = split_url(url)?;
+
+        if header(headers, "date").is_none() {
+            headers.push((String::from("date"), httpdate::fmt_http_date(SystemTime::now())));
+        }
+        if header(headers, "host").is_none() {
+            headers.push((String::from("host"), host.to_string()));
+        }
+
+        let mut signed: Vec<&str> = GENERIC_HEADERS.to_vec();
+        if matches!(method.as_str(), "post" | "put" | "patch") {
+            if header(headers, "content-length").is_none() {
+                headers.push((String::from("content-length"), body.len().to_string()));
+            }
+            if header(headers, "content-type").is_none() {
+                headers.push((String::from("content-type"), String::from("application/json")));
+            }
+            if header(headers, "x-content-sha256").is_none() {
+                headers.push((String::from("x-content-sha256"), STANDARD.encode(Sha256::digest(body))));
+            }
+            signed.extend(BODY_HEADERS);
+        }
+
+        // compose the signing string in the order of the signed headers
+        let mut lines: Vec<String> = Vec::new();
+        for name in &signed {
+            let value = match *name {
+                "(request-target)" => format!("{} {}", method, target),
+                _ => header(headers, name).unwrap_or_default().to_string(),
+            };
+            lines.push(format!("{}: {}", name, value));
+        }
+        let signature = self.signing_key.sign(lines.join("\n").as_bytes());
+
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("authorization"));
+        headers.push((
+            String::from("authorization"),
+            format!(
+                "Signature version=\"1\",keyId=\"{}\",algorithm=\"rsa-sha256\",headers=\"{}\",signature=\"{}\"",
+                self.key_id,
+                signed.join(" "),
+                STANDARD.encode(signature.to_bytes())
+            ),
+        ));
+        Ok(())
+    }
+}
+
+// Function to look up a header value, ignoring the case of the name
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+// Function to split a URL into the host and the request target, consisting of path and query
+fn split_url(url: &str) -> io::Result<(&str, &str)> {
+    let rest = url
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid URL: {}", url)))?;
+    match rest.find(['/', '?']) {
+        Some(index) if rest[index..].starts_with('/') => Ok((&rest[..index], &rest[index..])),
+        Some(_) => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid URL: {}", url))),
+        None => Ok((rest, "/")),
+    }
+}
+
+// Function to return a required value of a profile, or an error naming the missing key
+fn required<'a>(value: &'a Option<String>, key: &str, profile: &str) -> io::Result<&'a str> {
+    value.as_deref().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("Profile {} is missing the key {}", profile, key))
+    })
+}
@@ -0,0 +1,145 @@
+//! The terraform module generates the configuration of the Terraform OCI provider from a resolved profile.
+//! The `provider "oci"` block either references the profile of the config file or reads its fields from variables, the matching `terraform.tfvars` holds the values.
+//! API key profiles use the `APIKey` auth, session profiles the `SecurityToken` auth, which always reads the token through the config file profile.
+//! # Example
+//! ```rust
+//! use oci_cfg::config::Config;
+//! use oci_cfg::terraform::{provider, tfvars, Provider};
+//!
+//! let config = Config::parse("[PROD]\ntenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx\nregion=eu-frankfurt-1\n");
+//! let profile = config.profile("PROD").unwrap();
+//! println!("{}", provider(&profile, Provider::ConfigFile).unwrap());
+//! println!("{}", tfvars(&profile));
+//! ```
+use crate::config::ResolvedProfile;
+use crate::export::TF_VARS;
+use std::fmt;
+use std::io;
+
+/// represents the authentication of the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Auth {
+    ApiKey,
+    SecurityToken,
+}
+
+impl Auth {
+    /// returns the authentication of a profile, session profiles authenticate with a security token.
+    pub fn of(profile: &ResolvedProfile) -> Auth {
+        match profile.is_session() {
+            true => Auth::SecurityToken,
+            false => Auth::ApiKey,
+        }
+    }
+}
+
+impl fmt::Display for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Auth::ApiKey => write!(f, "APIKey"),
+            Auth::SecurityToken => write!(f, "SecurityToken"),
+        }
+    }
+}
+
+/// represents how the provider block gets the credentials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// the provider reads the profile from the config file with `config_file_profile`.
+    ConfigFile,
+    /// the provider reads the fields from the variables of `terraform.tfvars`.
+    Variables,
+}
+
+/// returns the `provider "oci"` block for a profile. The region is always read from a variable.
+/// Session profiles can't be written with variables, the security token is only read through the config file.
+pub fn provider(profile: &ResolvedProfile, style: Provider) -> io::Result<String> {
+    let auth = Auth::of(profile);
+    let mut fields: Vec<(&str, String)> = Vec::new();
+    match (style, auth) {
+        (Provider::ConfigFile, _) => {
+            fields.push(("auth", quote(&auth.to_string())));
+            fields.push(("config_file_profile", quote(&profile.name)));
+        }
+        (Provider::Variables, Auth::ApiKey) => {
+            for (_, variable) in variables(profile).iter().filter(|(key, _)| *key != "region") {
+                fields.push((variable, format!("var.{}", variable)));
+            }
+        }
+        (Provider::Variables, Auth::SecurityToken) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Profile {} uses a security token, which the provider only reads through config_file_profile", profile.name),
+            ))
+        }
+    }
+    fields.push(("region", String::from("var.region")));
+    Ok(format!("provider \"oci\" {{\n{}}}\n", block(&fields)))
+}
+
+/// returns the declarations of the variables used by the provider block and the tfvars.
+pub fn declarations(profile: &ResolvedProfile) -> String {
+    variables(profile)
+        .iter()
+        .map(|(_, variable)| format!("variable \"{}\" {{}}\n", variable))
+        .collect()
+}
+
+/// returns the content of a `terraform.tfvars` file with the values of the profile. Session profiles have no user, fingerprint or key.
+/// # Example
+/// ```rust
+/// use oci_cfg::config::ResolvedProfile;
+/// use oci_cfg::terraform::tfvars;
+///
+/// let profile = ResolvedProfile { region: Some(String::from("eu-frankfurt-1")), ..Default::default() };
+/// assert_eq!(tfvars(&profile), "region = \"eu-frankfurt-1\"\n");
+/// ```
+pub fn tfvars(profile: &ResolvedProfile) -> String {
+    let fields: Vec<(&str, String)> = variables(profile)
+        .into_iter()
+        .filter_map(|(key, variable)| value(profile, key).map(|value| (variable, quote(value))))
+        .collect();
+    block(&fields).lines().map(|line| format!("{}\n", line.trim_start())).collect()
+}
+
+// Function to return the keys of a profile with their variable names, the names of the Terraform variables without prefix
+fn variables(profile: &ResolvedProfile) -> Vec<(&'static str, &'static str)> {
+    let session = Auth::of(profile) == Auth::SecurityToken;
+    TF_VARS
+        .iter()
+        .filter(|(key, _)| !session || matches!(*key, "tenancy" | "region"))
+        .map(|(key, variable)| (*key, variable.trim_start_matches("TF_VAR_")))
+        .collect()
+}
+
+// Function to return the value of a key of a profile
+fn value<'a>(profile: &'a ResolvedProfile, key: &str) -> Option<&'a str> {
+    match key {
+        "user" => profile.user.as_deref(),
+        "fingerprint" => profile.fingerprint.as_deref(),
+        "key_file" => profile.key_file.as_deref(),
+        "tenancy" => profile.tenancy.as_deref(),
+        "region" => profile.region.as_deref(),
+        _ => None,
+    }
+}
+
+// Function to write indented fields with aligned equal signs, like terraform fmt
+fn block(fields: &[(&str, String)]) -> String {
+    let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    fields
+        .iter()
+        .map(|(name, value)| format!("  {:width$} = {}\n", name, value, width = width))
+        .collect()
+}
+
+// Function to quote a string for HCL, escaping quotes, backslashes, line breaks and template sequences
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+        .replace("${", "$${")
+        .replace("%{", "%%{");
+    format!("\"{}\"", escaped)
+}
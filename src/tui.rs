@@ -0,0 +1,414 @@
+//! The tui module, built with the `tui` feature, holds the state of the `oci-cfg-tui` terminal UI.
+//! It lists the profiles, shows the resolved values of the selected profile with secrets masked and highlights the findings of the doctor.
+//! Values are edited inline and saved through the config to the profile they come from, so comments and the layout of the file are kept.
+//! Values set by environment variables can't be edited here. The doctor runs when the file is loaded and saved, not when the selection moves.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::tui::{run, App};
+//!
+//! let mut app = App::new(String::from(".oci/config")).unwrap();
+//! let mut terminal = ratatui::init();
+//! let result = run(&mut terminal, &mut app);
+//! ratatui::restore();
+//! result.unwrap();
+//! ```
+use crate::config::{Config, DEFAULT_PROFILE};
+use crate::doctor::{diagnose_config, Finding, Report, Severity};
+use crate::resolve::{Layer, Resolver};
+use crate::secret::Secret;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::env;
+use std::io;
+
+// text shown in place of secrets
+static MASK: &str = "********";
+
+// pane that receives the keys
+#[derive(PartialEq)]
+enum Focus {
+    Profiles,
+    Values,
+}
+
+// a resolved value with its origin and the profile that defines it, secrets are masked
+struct Value {
+    key: String,
+    value: String,
+    origin: String,
+    section: Option<String>,
+    secret: bool,
+}
+
+/// holds the state of the terminal UI.
+pub struct App {
+    config_file: String,
+    config: Config,
+    profiles: ListState,
+    values: TableState,
+    rows: Vec<Value>,
+    findings: Vec<Finding>,
+    reports: Vec<Report>,
+    focus: Focus,
+    input: Option<Secret>,
+    status: String,
+    vars: fn(&str) -> Option<String>,
+}
+
+impl App {
+    /// loads the config file, runs the doctor and selects the first profile.
+    pub fn new(config_file: String) -> io::Result<App> {
+        let mut app = App {
+            config: Config::load(&config_file)?,
+            config_file,
+            profiles: ListState::default(),
+            values: TableState::default(),
+            rows: Vec::new(),
+            findings: Vec::new(),
+            reports: Vec::new(),
+            focus: Focus::Profiles,
+            input: None,
+            status: String::from("Tab switches the pane, Enter edits a value, q quits"),
+            vars: |name| env::var(name).ok(),
+        };
+        app.profiles.select(Some(0));
+        app.diagnose();
+        app.refresh();
+        Ok(app)
+    }
+
+    // Function to return the name of the selected profile
+    fn profile(&self) -> Option<String> {
+        self.profiles.selected().and_then(|index| self.config.profiles().get(index).cloned())
+    }
+
+    // Function to run the doctor on every profile, the doctor decodes the keys and may run passphrase commands, so it runs on load and save only
+    fn diagnose(&mut self) {
+        self.reports = self.config.profiles().iter().map(|profile| diagnose_config(&self.config, profile)).collect();
+    }
+
+    // Function to return whether the doctor found errors in a profile
+    fn unhealthy(&self, profile: &str) -> bool {
+        self.reports.iter().any(|report| report.profile == profile && !report.is_healthy())
+    }
+
+    // Function to resolve the selected profile and show its findings
+    fn refresh(&mut self) {
+        self.rows.clear();
+        self.findings.clear();
+        let Some(profile) = self.profile() else {
+            return;
+        };
+
+        let mut resolver = Resolver::new();
+        resolver.config_file = self.config_file.clone();
+        resolver.profile = Some(profile.clone());
+        match resolver.resolve_config(&self.config, self.vars) {
+            Ok(resolution) => {
+                // plain text passphrases are hidden by the resolution, they are listed masked to be edited
+                let profile = resolution.profile();
+                if profile.pass_phrase.is_some() && profile.pass_phrase_source.is_none() {
+                    let section = match self.config.get(&profile.name, "pass_phrase") {
+                        Some(_) => profile.name.clone(),
+                        None => DEFAULT_PROFILE.to_string(),
+                    };
+                    self.rows.push(Value {
+                        key: String::from("pass_phrase"),
+                        value: MASK.to_string(),
+                        origin: format!("{} profile", section),
+                        section: Some(section),
+                        secret: true,
+                    });
+                }
+                for resolved in resolution.values {
+                    let origin = resolved.origin.to_string();
+                    // values are written back to the profile they come from, other layers are edited outside of the file
+                    let section = match resolved.origin.layer {
+                        Layer::Profile => Some(profile.name.clone()),
+                        Layer::Default => Some(DEFAULT_PROFILE.to_string()),
+                        _ => None,
+                    };
+                    self.rows.push(Value { key: resolved.key, value: resolved.value, origin, section, secret: false });
+                }
+            }
+            Err(error) => self.status = error.to_string(),
+        }
+        self.findings = self
+            .reports
+            .iter()
+            .find(|report| report.profile == profile)
+            .map(|report| report.findings.clone())
+            .unwrap_or_default();
+        if self.values.selected().is_none_or(|index| index >= self.rows.len()) {
+            self.values.select(if self.rows.is_empty() { None } else { Some(0) });
+        }
+    }
+
+    // Function to return the most severe finding that concerns a value
+    fn severity(&self, row: &Value) -> Option<Severity> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.key == Some(row.key.as_str()))
+            .map(|finding| finding.severity)
+            .max()
+    }
+
+    // Function to return the selected row
+    fn row(&self) -> Option<&Value> {
+        self.values.selected().and_then(|index| self.rows.get(index))
+    }
+
+    // Function to save the edited value of the selected row to the profile that defines it
+    fn save(&mut self, value: &str) {
+        let Some(row) = self.row() else {
+            return;
+        };
+        let (key, origin) = (row.key.clone(), row.origin.clone());
+        let Some(section) = row.section.clone() else {
+            self.status = format!("{} comes from the {}, change it there", key, origin);
+            return;
+        };
+        self.config.set(&section, &key, value);
+        self.status = match self.config.save(&self.config_file) {
+            Ok(()) => format!("Saved {} of {} to {}", key, section, self.config_file),
+            Err(error) => format!("Failed to save {}: {}", self.config_file, error),
+        };
+        self.diagnose();
+        self.refresh();
+    }
+
+    /// handles a key, returns false to quit.
+    pub fn key(&mut self, code: KeyCode) -> bool {
+        if let Some(input) = self.input.as_mut() {
+            match code {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => {
+                    if let Some(value) = self.input.take() {
+                        self.save(value.expose());
+                    }
+                }
+                KeyCode::Esc => self.input = None,
+                _ => {}
+            }
+            return true;
+        }
+        match (code, &self.focus) {
+            (KeyCode::Char('q'), _) | (KeyCode::Esc, _) => return false,
+            (KeyCode::Tab, Focus::Profiles) => self.focus = Focus::Values,
+            (KeyCode::Tab, Focus::Values) => self.focus = Focus::Profiles,
+            (KeyCode::Down, Focus::Profiles) => {
+                let last = self.config.profiles().len().saturating_sub(1);
+                self.profiles.select(self.profiles.selected().map(|index| (index + 1).min(last)));
+                self.refresh();
+            }
+            (KeyCode::Up, Focus::Profiles) => {
+                self.profiles.select(self.profiles.selected().map(|index| index.saturating_sub(1)));
+                self.refresh();
+            }
+            (KeyCode::Down, Focus::Values) => self.values.select_next(),
+            (KeyCode::Up, Focus::Values) => self.values.select_previous(),
+            (KeyCode::Enter, Focus::Values) => match self.row() {
+                Some(row) if row.section.is_none() => self.status = format!("{} comes from the {}, change it there", row.key, row.origin),
+                Some(row) => self.input = Some(if row.secret { Secret::default() } else { Secret::from(row.value.as_str()) }),
+                None => {}
+            },
+            _ => {}
+        }
+        true
+    }
+
+    // Function to return the status line, with the value being edited
+    fn status_line(&self) -> String {
+        match &self.input {
+            // typed secrets are masked, only their length is shown
+            Some(input) if self.row().is_some_and(|row| row.secret) => {
+                format!("New value: {}_  (Enter saves, Esc cancels)", "*".repeat(input.expose().chars().count()))
+            }
+            Some(input) => format!("New value: {}_  (Enter saves, Esc cancels)", input.expose()),
+            None => self.status.clone(),
+        }
+    }
+
+    /// draws the panes of the UI.
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [main, findings, status] =
+            Layout::vertical([Constraint::Min(5), Constraint::Length(8), Constraint::Length(1)]).areas(frame.area());
+        let [profiles, values] = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(main);
+        let focused = |focus: Focus| match self.focus == focus {
+            true => Style::default().fg(Color::Cyan),
+            false => Style::default(),
+        };
+
+        let items: Vec<ListItem> = self
+            .config
+            .profiles()
+            .into_iter()
+            .map(|profile| match self.unhealthy(&profile) {
+                true => ListItem::new(format!("{} !", profile)).style(Style::default().fg(Color::Red)),
+                false => ListItem::new(profile),
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Profiles").border_style(focused(Focus::Profiles)))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, profiles, &mut self.profiles);
+
+        let rows: Vec<Row> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let style = match self.severity(row) {
+                    Some(Severity::Error) => Style::default().fg(Color::Red),
+                    Some(Severity::Warning) => Style::default().fg(Color::Yellow),
+                    _ => Style::default(),
+                };
+                Row::new(vec![row.key.clone(), row.value.clone(), row.origin.clone()]).style(style)
+            })
+            .collect();
+        let table = Table::new(rows, [Constraint::Percentage(20), Constraint::Percentage(45), Constraint::Percentage(35)])
+            .header(Row::new(vec!["Key", "Value", "Origin"]).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(Block::default().borders(Borders::ALL).title("Resolved values").border_style(focused(Focus::Values)))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, values, &mut self.values);
+
+        let lines: Vec<Line> = self
+            .findings
+            .iter()
+            .map(|finding| {
+                let color = match finding.severity {
+                    Severity::Error => Color::Red,
+                    Severity::Warning => Color::Yellow,
+                    Severity::Info => Color::Blue,
+                };
+                Line::from(vec![
+                    Span::styled(format!("{} [{}] ", finding.severity, finding.check), Style::default().fg(color)),
+                    Span::raw(format!("{} ({})", finding.message, finding.fix)),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Doctor")), findings);
+
+        frame.render_widget(Paragraph::new(self.status_line()), status);
+    }
+}
+
+/// draws the UI and handles keys until the user quits.
+pub fn run(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Function to write a config file and load it without environment variables
+    fn app(name: &str, content: &str) -> App {
+        let config_file = env::temp_dir().join(format!("oci_cfg_test_tui_{}_{}", name, std::process::id()));
+        fs::write(&config_file, content).unwrap();
+        let mut app = App::new(config_file.to_string_lossy().to_string()).unwrap();
+        app.vars = |_| None;
+        app.refresh();
+        app
+    }
+
+    // Function to select the row of a key in the values pane
+    fn select(app: &mut App, key: &str) {
+        let index = app.rows.iter().position(|row| row.key == key).unwrap();
+        app.focus = Focus::Values;
+        app.values.select(Some(index));
+    }
+
+    #[test]
+    fn save_to_section() {
+        let mut app = app("save", "[DEFAULT]\n# home region\nregion=mars-north-1\n\n[DEV]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n");
+        app.key(KeyCode::Down);
+        assert_eq!(app.profile().as_deref(), Some("DEV"));
+        select(&mut app, "region");
+        assert_eq!(app.row().unwrap().section.as_deref(), Some(DEFAULT_PROFILE));
+        assert_eq!(app.severity(app.row().unwrap()), Some(Severity::Warning));
+        assert_eq!(app.severity(&app.rows[app.rows.iter().position(|row| row.key == "user").unwrap()]), None);
+
+        app.save("us-ashburn-1");
+        let content = fs::read_to_string(&app.config_file).unwrap();
+        assert_eq!(
+            content,
+            "[DEFAULT]\n# home region\nregion=us-ashburn-1\n\n[DEV]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n"
+        );
+        assert!(app.status.starts_with("Saved region of DEFAULT"));
+        fs::remove_file(&app.config_file).unwrap();
+    }
+
+    #[test]
+    fn environment_refused() {
+        let mut app = app("environment", "[DEFAULT]\nregion=us-ashburn-1\n");
+        app.vars = |name| (name == "OCI_CLI_REGION").then(|| String::from("eu-frankfurt-1"));
+        app.refresh();
+        select(&mut app, "region");
+        assert_eq!(app.row().unwrap().value, "eu-frankfurt-1");
+        assert!(app.row().unwrap().section.is_none());
+
+        assert!(app.key(KeyCode::Enter));
+        assert!(app.input.is_none());
+        assert!(app.status.starts_with("region comes from the"));
+        app.save("ap-tokyo-1");
+        assert_eq!(fs::read_to_string(&app.config_file).unwrap(), "[DEFAULT]\nregion=us-ashburn-1\n");
+        fs::remove_file(&app.config_file).unwrap();
+    }
+
+    #[test]
+    fn masked_secrets() {
+        let mut app = app("masked", "[DEFAULT]\nregion=us-ashburn-1\npass_phrase=s3cr3t\n");
+        assert!(app.rows.iter().all(|row| !row.value.contains("s3cr3t")));
+        select(&mut app, "pass_phrase");
+        assert_eq!(app.row().unwrap().value, MASK);
+
+        app.key(KeyCode::Enter);
+        assert_eq!(app.input.as_ref().map(Secret::expose), Some(""));
+        app.key(KeyCode::Char('a'));
+        app.key(KeyCode::Char('b'));
+        assert_eq!(app.status_line(), "New value: **_  (Enter saves, Esc cancels)");
+        app.key(KeyCode::Enter);
+        assert!(fs::read_to_string(&app.config_file).unwrap().contains("pass_phrase=ab\n"));
+        assert!(!app.status_line().contains("ab"));
+        fs::remove_file(&app.config_file).unwrap();
+    }
+
+    #[test]
+    fn key_input() {
+        let mut app = app("keys", "[DEFAULT]\nregion=us-ashburn-1\n");
+        assert!(app.key(KeyCode::Tab));
+        assert!(app.focus == Focus::Values);
+        select(&mut app, "region");
+        app.key(KeyCode::Enter);
+        assert_eq!(app.status_line(), "New value: us-ashburn-1_  (Enter saves, Esc cancels)");
+        app.key(KeyCode::Backspace);
+        app.key(KeyCode::Char('2'));
+        assert_eq!(app.input.as_ref().map(Secret::expose), Some("us-ashburn-2"));
+        app.key(KeyCode::Esc);
+        assert!(app.input.is_none());
+        assert_eq!(fs::read_to_string(&app.config_file).unwrap(), "[DEFAULT]\nregion=us-ashburn-1\n");
+
+        app.key(KeyCode::Enter);
+        app.key(KeyCode::Backspace);
+        app.key(KeyCode::Char('2'));
+        app.key(KeyCode::Enter);
+        assert_eq!(fs::read_to_string(&app.config_file).unwrap(), "[DEFAULT]\nregion=us-ashburn-2\n");
+        assert!(!app.key(KeyCode::Char('q')));
+        fs::remove_file(&app.config_file).unwrap();
+    }
+}
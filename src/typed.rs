@@ -0,0 +1,113 @@
+//! The typed module loads a profile into a struct that a team defines with its own keys, it is built with the `serde` feature.
+//! The `OciProfile` trait reads the profile with the keys it inherits from DEFAULT and validates it before it is returned.
+//! With the `derive` feature `#[derive(OciProfile)]` generates the validation from `#[oci(...)]` attributes of the fields, using the checks of this module.
+//! # Example
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use oci_cfg::typed::OciProfile;
+//! use oci_cfg::OciProfile;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, OciProfile)]
+//! struct Profile {
+//!     #[oci(region)]
+//!     region: String,
+//!     #[oci(ocid = "compartment")]
+//!     compartment_id: Option<String>,
+//! }
+//!
+//! let config = "[DEFAULT]\nregion=eu-frankfurt-1\n\n[DEV]\ncompartment_id=ocid1.compartment.oc1..aaaaaaaaxxxxxx\n";
+//! let profile = Profile::from_config(config, "DEV").unwrap();
+//! assert_eq!(profile.region, "eu-frankfurt-1");
+//! assert!(Profile::from_config("[DEV]\nregion=eu-frankfurt-1\ncompartment_id=ocid1.user.oc1..aaaaaaaaxxxxxx\n", "DEV").is_err());
+//! # }
+//! ```
+use crate::file::expand;
+use crate::ini::profile_from_str;
+use crate::ocid::Ocid;
+use crate::region::identifiers;
+use crate::{DIR, NAME};
+use directories::UserDirs;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// represents a profile struct that is validated after it was read.
+pub trait OciProfile: DeserializeOwned {
+    /// checks the values of the profile, derived implementations check the fields with `#[oci(...)]` attributes.
+    fn validate(&self) -> io::Result<()>;
+
+    /// reads a profile from the content of a config file and validates it, keys missing in the profile are inherited from DEFAULT.
+    fn from_config(content: &str, name: &str) -> io::Result<Self> {
+        let profile: Self = profile_from_str(content, name)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// reads a profile from a config file, relative paths are resolved against the user's home.
+    fn load_from(config_file: &str, name: &str) -> io::Result<Self> {
+        let home = UserDirs::new()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))?;
+        let content = fs::read_to_string(home.home_dir().join(config_file))?;
+        Self::from_config(&content, name)
+    }
+
+    /// reads a profile from the config file in the user's home.
+    fn load(name: &str) -> io::Result<Self> {
+        Self::load_from(&format!("{}/{}", DIR, NAME), name)
+    }
+}
+
+/// represents a field that holds a text value, `None` has no value to validate.
+pub trait Field {
+    fn value(&self) -> Option<&str>;
+}
+
+impl Field for String {
+    fn value(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl Field for PathBuf {
+    fn value(&self) -> Option<&str> {
+        self.to_str()
+    }
+}
+
+impl<T: Field> Field for Option<T> {
+    fn value(&self) -> Option<&str> {
+        self.as_ref().and_then(Field::value)
+    }
+}
+
+/// checks that a field holds an OCID of a resource type.
+pub fn check_ocid(field: &str, value: &str, resource_type: &str) -> io::Result<()> {
+    Ocid::expect(value, resource_type)
+        .map(|_| ())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", field, error)))
+}
+
+/// checks that a field holds a known region identifier.
+pub fn check_region(field: &str, value: &str) -> io::Result<()> {
+    match identifiers().iter().any(|region| region == value) {
+        true => Ok(()),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: The region {} is unknown, use a region identifier like us-ashburn-1", field, value),
+        )),
+    }
+}
+
+/// checks that a field holds a file path, which has to exist with `must_exist`. A leading `~/` is resolved against the user's home.
+pub fn check_path(field: &str, value: &str, must_exist: bool) -> io::Result<()> {
+    if value.trim().is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{}: The path is empty", field)));
+    }
+    if must_exist && !expand(value).exists() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("{}: The file {} does not exist", field, value)));
+    }
+    Ok(())
+}
@@ -0,0 +1,238 @@
+//! The wizard module guides a new user through the setup of a profile, like `oci setup config`.
+//! It asks for the profile name, the user and tenancy OCIDs and the region and validates every answer before it moves on.
+//! Regions are completed from the region table, a key pair is generated on request in a directory of the profile and its public key is printed for the upload to the console.
+//! A passphrase is never written in plain text, the profile references it through a variable, a file or a command, or the user adds the reference later.
+//! The wizard reads answers from any reader and writes prompts to any writer, so tests and scripts can drive it.
+//! The passphrase is held as a secret, with `terminal` the echo of the terminal is turned off while it is typed.
+//! # Example
+//! ```rust,no_run
+//! use oci_cfg::wizard::Wizard;
+//! use std::io::{self, IsTerminal};
+//!
+//! let mut wizard = Wizard::new(io::stdin().lock(), io::stdout());
+//! wizard.terminal = io::stdin().is_terminal();
+//! let profile = wizard.run().unwrap();
+//! println!("Profile {} written", profile.name);
+//! ```
+use crate::config::{Config, ResolvedProfile, DEFAULT_PROFILE};
+use crate::file::{create_dir, expand};
+use crate::keys::{fingerprint, generate, load, KeySize, PRIVATE_KEY, PUBLIC_KEY};
+use crate::ocid::Ocid;
+use crate::passphrase::PassPhraseSource;
+use crate::secret::Secret;
+use crate::region::{complete, lookup};
+use crate::{DIR, NAME};
+use directories::UserDirs;
+use rsa::RsaPublicKey;
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use zeroize::Zeroize;
+
+/// contains the prompts, the answers and the files of a setup.
+pub struct Wizard<R, W> {
+    input: R,
+    output: W,
+    pub config_file: String,
+    pub key_dir: String,
+    /// turns the echo of the terminal off while secrets are typed, for input read from stdin of a terminal.
+    pub terminal: bool,
+}
+
+impl<R: BufRead, W: Write> Wizard<R, W> {
+    /// creates a wizard that writes the profile to the config file in the user's home and keys to a directory named after the profile below its directory.
+    pub fn new(input: R, output: W) -> Self {
+        Self {
+            input,
+            output,
+            config_file: format!("{}/{}", DIR, NAME),
+            key_dir: DIR.to_string(),
+            terminal: false,
+        }
+    }
+
+    /// asks for the values of a profile, writes it to the config file and returns it.
+    /// Invalid answers are reported and asked again, the setup fails when the input ends.
+    pub fn run(&mut self) -> io::Result<ResolvedProfile> {
+        let mut config = match Config::load(&self.config_file) {
+            Ok(config) => config,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(error) => return Err(error),
+        };
+
+        let profiles = config.profiles();
+        let name = self.ask(&format!("Name of the profile [{}]", DEFAULT_PROFILE), |answer| {
+            let name = if answer.is_empty() { DEFAULT_PROFILE } else { answer };
+            match profiles.iter().any(|profile| profile == name) {
+                true => Err(format!("Profile {} already exists, choose another name", name)),
+                false => Ok(name.to_string()),
+            }
+        })?;
+        let user = self.ask("User OCID", |answer| {
+            Ocid::expect(answer, "user").map(|_| answer.to_string()).map_err(|error| error.to_string())
+        })?;
+        let tenancy = self.ask("Tenancy OCID", |answer| {
+            let tenancy = Ocid::expect(answer, "tenancy").map_err(|error| error.to_string())?;
+            let realm = Ocid::parse(&user).map(|user| user.realm).unwrap_or_default();
+            match tenancy.realm == realm {
+                true => Ok(answer.to_string()),
+                false => Err(format!("The tenancy is in realm {}, but the user in realm {}", tenancy.realm, realm)),
+            }
+        })?;
+        let region = self.ask("Region, a code like FRA or an identifier, partial identifiers are completed", |answer| {
+            if let Some(region) = lookup(answer) {
+                return Ok(region);
+            }
+            match complete(answer).as_slice() {
+                [] => Err(format!("Unknown region {}", answer)),
+                [region] => Ok(region.clone()),
+                regions => Err(format!("{} matches {}", answer, regions.join(", "))),
+            }
+        })?;
+        writeln!(self.output, "Using region {}", region)?;
+
+        let generate_key = self.ask("Generate a new API signing key pair? [Y/n]", |answer| {
+            match answer.to_lowercase().as_str() {
+                "" | "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                _ => Err(String::from("Answer y or n")),
+            }
+        })?;
+        let pass_phrase = self.ask_secret("Passphrase of the private key, empty for none")?;
+        let pass_phrase = Some(pass_phrase).filter(|pass_phrase| !pass_phrase.expose().is_empty());
+        let pass_phrase_source = match pass_phrase {
+            Some(_) => self.ask(
+                "Passphrase reference, pass_phrase_env=<variable>, pass_phrase_file=<path> or pass_phrase_command=<command>, empty to add it later",
+                |answer| {
+                    if answer.is_empty() {
+                        return Ok(None);
+                    }
+                    answer
+                        .split_once('=')
+                        .and_then(|(key, value)| PassPhraseSource::from_entry(key.trim(), value.trim()))
+                        .map(Some)
+                        .ok_or_else(|| String::from("Use pass_phrase_env, pass_phrase_file or pass_phrase_command, the passphrase isn't stored in the file"))
+                },
+            )?,
+            None => None,
+        };
+
+        let (key_file, key_fingerprint) = if generate_key {
+            // every profile gets its own directory, so existing keys of other profiles are never replaced
+            let key_dir = Path::new(&self.key_dir).join(&name);
+            let key_fingerprint = generate(&key_dir.to_string_lossy(), KeySize::Rsa2048, pass_phrase.as_ref().map(Secret::expose), false)?;
+            writeln!(self.output, "Key pair written to {}", home()?.join(&key_dir).display())?;
+            let public_key = fs::read_to_string(home()?.join(key_dir.join(PUBLIC_KEY)))?;
+            writeln!(self.output, "Upload the public key in the console under Identity > Users > API Keys:\n{}", public_key.trim())?;
+            (home()?.join(key_dir.join(PRIVATE_KEY)).to_string_lossy().to_string(), key_fingerprint)
+        } else {
+            self.ask("Path of the private key", |answer| {
+                let key = load(answer, pass_phrase.as_ref().map(Secret::expose)).map_err(|error| error.to_string())?;
+                let key_fingerprint = fingerprint(&RsaPublicKey::from(&key)).map_err(|error| error.to_string())?;
+                Ok((expand(answer).to_string_lossy().to_string(), key_fingerprint))
+            })?
+        };
+
+        for (key, value) in [
+            ("user", &user),
+            ("fingerprint", &key_fingerprint),
+            ("key_file", &key_file),
+            ("tenancy", &tenancy),
+            ("region", &region),
+        ] {
+            config.set(&name, key, value);
+        }
+        match (&pass_phrase, &pass_phrase_source) {
+            (_, Some(source)) => config.set_pass_phrase_source(&name, source),
+            (Some(_), None) => writeln!(
+                self.output,
+                "The passphrase isn't stored, add pass_phrase_env, pass_phrase_file or pass_phrase_command to profile {} to supply it",
+                name
+            )?,
+            (None, None) => {}
+        }
+        if let Some(parent) = home()?.join(&self.config_file).parent() {
+            create_dir(parent)?;
+        }
+        config.save(&self.config_file)?;
+        writeln!(self.output, "Profile {} written to {}", name, self.config_file)?;
+
+        config
+            .profile(&name)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Profile {} not found", name)))
+    }
+
+    // Function to ask for a secret without echo on a terminal, the typed line is zeroized once it is wrapped
+    fn ask_secret(&mut self, question: &str) -> io::Result<Secret> {
+        write!(self.output, "{}: ", question)?;
+        self.output.flush()?;
+        if self.terminal {
+            set_echo(false)?;
+        }
+        let mut answer = String::new();
+        let read = self.input.read_line(&mut answer);
+        if self.terminal {
+            set_echo(true)?;
+            writeln!(self.output)?;
+        }
+        let secret = Secret::from(answer.trim_end_matches(['\r', '\n']));
+        answer.zeroize();
+        match read? {
+            0 => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "The setup was cancelled")),
+            _ => Ok(secret),
+        }
+    }
+
+    // Function to ask a question until the answer is valid, the answer is trimmed before validation
+    fn ask<T, F>(&mut self, question: &str, validate: F) -> io::Result<T>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        loop {
+            write!(self.output, "{}: ", question)?;
+            self.output.flush()?;
+            let mut answer = String::new();
+            if self.input.read_line(&mut answer)? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "The setup was cancelled"));
+            }
+            match validate(answer.trim()) {
+                Ok(value) => return Ok(value),
+                Err(message) => writeln!(self.output, "{}", message)?,
+            }
+        }
+    }
+}
+
+// Function to turn the echo of the terminal on stdin on or off
+#[cfg(unix)]
+fn set_echo(enabled: bool) -> io::Result<()> {
+    // SAFETY: termios is plain data that tcgetattr fills before it is changed and written back
+    unsafe {
+        let mut termios: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        match enabled {
+            true => termios.c_lflag |= libc::ECHO,
+            false => termios.c_lflag &= !libc::ECHO,
+        }
+        if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+// Function to turn the echo of the terminal on or off, other platforms keep the echo
+#[cfg(not(unix))]
+fn set_echo(_enabled: bool) -> io::Result<()> {
+    Ok(())
+}
+
+// Function to return the user's home directory
+fn home() -> io::Result<std::path::PathBuf> {
+    UserDirs::new()
+        .map(|dirs| dirs.home_dir().to_path_buf())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Failed to get user's home directory"))
+}
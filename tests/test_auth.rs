@@ -0,0 +1,80 @@
+mod common;
+
+use common::{certificate, metadata_stub, temp_dir, token, PRIVATE_KEY};
+use oci_cfg::auth::{AuthProvider, InstancePrincipalProvider, ProviderChain};
+use std::io;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn profile_chain() {
+    let dir = temp_dir("auth");
+    std::fs::write(dir.join("key.pem"), PRIVATE_KEY).unwrap();
+    let config = format!(
+        "[DEFAULT]\nregion=us-ashburn-1\n\n[DEV]\nuser=ocid1.user.oc1..aaaaaaaauser\nfingerprint=aa:bb\ntenancy=ocid1.tenancy.oc1..aaaaaaaatenancy\nkey_file={}\n",
+        dir.join("key.pem").display()
+    );
+    std::fs::write(dir.join("config"), config).unwrap();
+
+    let mut chain = ProviderChain::new();
+    chain.profile = Some(String::from("DEV"));
+    chain.config_file = dir.join("config").display().to_string();
+    let mut provider = chain.provider().unwrap();
+    assert_eq!(provider.key_id().unwrap(), "ocid1.tenancy.oc1..aaaaaaaatenancy/ocid1.user.oc1..aaaaaaaauser/aa:bb");
+    assert_eq!(provider.region().as_deref(), Some("us-ashburn-1"));
+
+    let mut headers: Vec<(String, String)> = Vec::new();
+    provider.sign("GET", "https://iaas.us-ashburn-1.oraclecloud.com/20160918/vcns", &mut headers, b"").unwrap();
+    assert!(headers.iter().any(|(name, _)| name == "authorization"));
+    provider.refresh().unwrap();
+
+    let mut chain = ProviderChain::new();
+    chain.profile = Some(String::from("MISSING"));
+    chain.config_file = dir.join("config").display().to_string();
+    assert!(chain.provider().is_err());
+
+    // the profile selected by the environment is used before the DEFAULT profile and its errors are returned
+    std::fs::write(dir.join("oci_cli_rc"), "").unwrap();
+    let chain = |config: &str| {
+        std::fs::write(dir.join("config"), config).unwrap();
+        let mut chain = ProviderChain::new();
+        chain.config_file = dir.join("config").display().to_string();
+        chain.cli_rc_file = dir.join("oci_cli_rc").display().to_string();
+        chain.provider()
+    };
+    let dev = format!(
+        "[DEV]\nuser=ocid1.user.oc1..aaaaaaaauser\nfingerprint=aa:bb\ntenancy=ocid1.tenancy.oc1..aaaaaaaatenancy\nkey_file={}\n",
+        dir.join("key.pem").display()
+    );
+    std::env::set_var("OCI_CLI_PROFILE", "DEV");
+    assert_eq!(chain(&dev).unwrap().key_id().unwrap(), "ocid1.tenancy.oc1..aaaaaaaatenancy/ocid1.user.oc1..aaaaaaaauser/aa:bb");
+    let error = chain(&dev.replace("key.pem", "missing.pem")).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    std::env::remove_var("OCI_CLI_PROFILE");
+
+    // without selected profile, environment or principals the DEFAULT profile is used when it exists
+    assert!(chain(&dev.replace("[DEV]", "[DEFAULT]")).is_ok());
+    assert_eq!(chain(&dev).err().unwrap().kind(), io::ErrorKind::NotFound);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn instance_principal_provider() {
+    let url = metadata_stub(certificate("ocid1.tenancy.oc1..aaaaaaaaxxxxxx"));
+    let requests: Arc<Mutex<Vec<(String, String)>>> = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+    let session_token = token(r#""sub":"ocid1.instance.oc1.phx.xxxxxx""#, 3600);
+    let response = format!(r#"{{"token":"{}"}}"#, session_token);
+    let transport = Box::new(move |url: &str, headers: &[(String, String)], _body: &str| {
+        let authorization = headers.iter().find(|(name, _)| name == "authorization").unwrap().1.clone();
+        recorded.lock().unwrap().push((url.to_string(), authorization));
+        Ok(response.clone())
+    });
+
+    let provider = InstancePrincipalProvider::new(&url, transport).unwrap();
+    assert_eq!(provider.key_id().unwrap(), format!("ST${}", session_token));
+    assert_eq!(provider.region().as_deref(), Some("us-phoenix-1"));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].0, "https://auth.us-phoenix-1.oraclecloud.com/v1/x509");
+    assert!(requests[0].1.contains("keyId=\"ocid1.tenancy.oc1..aaaaaaaaxxxxxx/fed-x509/"));
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "cli")]
+mod common;
+
+use std::process::Command;
+
+// runs the binary against the given config file and returns the exit code and stdout
+fn oci_cfg(config: &std::path::Path, args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_oci-cfg"))
+        .arg("--config")
+        .arg(config)
+        .args(args)
+        .output()
+        .unwrap();
+    (output.status.code().unwrap(), String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[test]
+fn commands() {
+    let dir = common::temp_dir("cli");
+    let config = dir.join("config");
+    std::fs::write(&config, "[DEFAULT]\nregion=us-ashburn-1\n").unwrap();
+
+    let add = [
+        "add", "ADMIN", "--user", "ocid1.user.oc1..aaaaaaaaxxxxxx", "--fingerprint", "aa:bb", "--key-file", "/nonexistent.pem",
+        "--tenancy", "ocid1.tenancy.oc1..aaaaaaaaxxxxxx", "--region", "eu-frankfurt-1",
+    ];
+    assert_eq!(oci_cfg(&config, &add).0, 0);
+    assert_eq!(oci_cfg(&config, &add).0, 2);
+    assert_eq!(oci_cfg(&config, &["set", "ADMIN", "region", "uk-london-1"]).0, 0);
+    assert_eq!(oci_cfg(&config, &["rename", "ADMIN", "LONDON"]).0, 0);
+
+    let (code, list) = oci_cfg(&config, &["list", "--json"]);
+    assert_eq!(code, 0);
+    assert_eq!(list.trim(), r#"{"active":"DEFAULT","profiles":["DEFAULT","LONDON"]}"#);
+
+    let (_, show) = oci_cfg(&config, &["show", "LONDON", "--json"]);
+    let show: serde_json::Value = serde_json::from_str(&show).unwrap();
+    assert_eq!(show["values"][4]["value"], "uk-london-1");
+    assert!(show["values"][4]["origin"].as_str().unwrap().ends_with("config:9"));
+
+    assert_eq!(oci_cfg(&config, &["doctor", "LONDON"]).0, 1);
+    assert_eq!(oci_cfg(&config, &["validate"]).0, 0);
+    assert!(oci_cfg(&config, &["env", "LONDON", "--shell", "fish"]).1.contains("set -gx OCI_CLI_REGION 'uk-london-1'\n"));
+    assert_eq!(oci_cfg(&config, &["remove", "LONDON"]).0, 0);
+    assert_eq!(std::fs::read_to_string(&config).unwrap(), "[DEFAULT]\nregion=us-ashburn-1\n");
+    assert!(oci_cfg(&config, &["regions"]).1.contains("eu-frankfurt-1"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn completion() {
+    let dir = common::temp_dir("cli_completion");
+    let config = dir.join("config");
+    let marker = dir.join("marker");
+    std::fs::write(
+        &config,
+        format!(
+            "[DEFAULT]\nregion=us-ashburn-1\npass_phrase_command=touch {}\n\n[PROD_FRA_ADMIN]\nregion=eu-frankfurt-1\n",
+            marker.display()
+        ),
+    )
+    .unwrap();
+    std::fs::write(dir.join("other"), "[PROD_LHR]\nregion=uk-london-1\n").unwrap();
+    let complete = |config_file: &str, args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oci-cfg"))
+            .current_dir(&dir)
+            .env("COMPLETE", "fish")
+            .env("OCI_CLI_CONFIG_FILE", config_file)
+            .args(["--", "oci-cfg"])
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    assert_eq!(complete(config.to_str().unwrap(), &["show", "PROD"]), "PROD_FRA_ADMIN\teu-frankfurt-1\n");
+    assert_eq!(complete("config", &["show", "D"]), "DEFAULT\tus-ashburn-1\n");
+    assert!(!marker.exists(), "completion ran the passphrase command");
+    assert_eq!(complete("config", &["--config", "other", "show", "PROD"]), "PROD_LHR\tuk-london-1\n");
+    assert_eq!(complete("config", &["--config=other", "show", "PROD"]), "PROD_LHR\tuk-london-1\n");
+    assert_eq!(complete("config", &["add", "LONDON", "--region", "uk-l"]), "uk-london-1\n");
+    assert!(complete("config", &["add", "LONDON", "--region", "FR"]).contains("FRA\teu-frankfurt-1"));
+    assert!(complete("config", &["doc"]).starts_with("doctor"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,77 @@
+use oci_cfg::cli_rc::{active_profile, switch_profile, CliRc, COMMAND_ALIASES};
+use oci_cfg::resolve::Resolver;
+
+static CONTENT: &str = "[OCI_CLI_SETTINGS]
+default_profile=IAD
+
+[OCI_CLI_COMMAND_ALIASES]
+# list compartments with a short command
+ls = iam compartment list
+rm = os.object.delete
+
+[OCI_CLI_PARAM_ALIASES]
+--ad = --availability-domain
+
+[OCI_CLI_CANNED_QUERIES]
+get_id_and_display_name_from_list=data[*].{id: id, \"display-name\": \"display-name\"}
+
+[DEFAULT]
+compartment-id = ocid1.compartment.oc1..aaaaaaaadefault
+output = table
+
+[IAD]
+compartment-id = ocid1.compartment.oc1..aaaaaaaaiad
+";
+
+#[test]
+fn sections() {
+    let rc = CliRc::parse(CONTENT);
+    assert_eq!(rc.default_profile(), Some("IAD"));
+    assert_eq!(rc.default_profile_line(), Some(2));
+    assert_eq!(rc.command_aliases()[0], (String::from("ls"), String::from("iam compartment list")));
+    assert_eq!(rc.param_aliases()[0].1, "--availability-domain");
+    assert_eq!(
+        rc.canned_query("get_id_and_display_name_from_list"),
+        Some("data[*].{id: id, \"display-name\": \"display-name\"}")
+    );
+    let defaults = rc.defaults("IAD");
+    assert_eq!(defaults[0], (String::from("compartment-id"), String::from("ocid1.compartment.oc1..aaaaaaaaiad")));
+    assert_eq!(defaults[1].0, "output");
+}
+
+#[test]
+fn round_trip() {
+    let mut rc = CliRc::parse(CONTENT);
+    assert_eq!(rc.to_string(), CONTENT);
+
+    rc.set_default_profile("FRA");
+    rc.set_command_alias("ls", "iam compartment list --all");
+    assert!(rc.remove(COMMAND_ALIASES, "rm"));
+    rc.set_canned_query("names", "data[*].name");
+    let expected = CONTENT
+        .replace("default_profile=IAD", "default_profile=FRA")
+        .replace("ls = iam compartment list\nrm = os.object.delete", "ls = iam compartment list --all")
+        .replace("\"display-name\"}\n", "\"display-name\"}\nnames=data[*].name\n");
+    assert_eq!(rc.to_string(), expected);
+}
+
+#[test]
+fn switching() {
+    let dir = std::env::temp_dir().join(format!("oci_cfg_test_active_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_file = dir.join("config");
+    let cli_rc_file = dir.join("oci_cli_rc");
+    std::fs::write(&config_file, "[DEFAULT]\nregion=us-ashburn-1\n\n[FRA]\nregion=eu-frankfurt-1\n").unwrap();
+    let (config_file, cli_rc_file) = (config_file.to_str().unwrap(), cli_rc_file.to_str().unwrap());
+
+    assert_eq!(active_profile(cli_rc_file).unwrap(), "DEFAULT");
+    switch_profile(config_file, cli_rc_file, "FRA").unwrap();
+    assert_eq!(active_profile(cli_rc_file).unwrap(), "FRA");
+    assert!(switch_profile(config_file, cli_rc_file, "MISSING").is_err());
+
+    let mut resolver = Resolver::new();
+    resolver.config_file = config_file.to_string();
+    resolver.cli_rc_file = cli_rc_file.to_string();
+    assert_eq!(resolver.resolve().unwrap().get("region"), Some("eu-frankfurt-1"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,29 @@
+use oci_cfg::config::Config;
+
+static CONFIG: &str = "# managed by hand\n[DEFAULT]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\nregion = us-ashburn-1\n\n[ADMIN_USER]\nregion: eu-frankfurt-1\nkey_file=~/.oci/oci_api_key.pem\n";
+
+#[test]
+fn round_trip() {
+    assert_eq!(Config::parse(CONFIG).to_string(), CONFIG);
+}
+
+#[test]
+fn default_inheritance() {
+    let config = Config::parse(CONFIG);
+    assert_eq!(config.profiles(), vec!["DEFAULT", "ADMIN_USER"]);
+    let admin = config.profile("ADMIN_USER").unwrap();
+    assert_eq!(admin.user.as_deref(), Some("ocid1.user.oc1..aaaaaaaaxxxxxx"));
+    assert_eq!(admin.region.as_deref(), Some("eu-frankfurt-1"));
+    assert!(config.profile("MISSING").is_none());
+}
+
+#[test]
+fn remove_and_rename_profiles() {
+    let mut config = Config::parse("[DEFAULT]\nregion=us-ashburn-1\n\n# admin\n[ADMIN_USER]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n\n[FRA]\nregion=eu-frankfurt-1");
+    assert!(config.rename_profile("ADMIN_USER", "ADMIN"));
+    assert!(!config.rename_profile("ADMIN", "FRA"));
+    assert!(!config.rename_profile("MISSING", "OTHER"));
+    assert!(config.remove_profile("FRA"));
+    assert!(!config.remove_profile("FRA"));
+    assert_eq!(config.to_string(), "[DEFAULT]\nregion=us-ashburn-1\n\n# admin\n[ADMIN]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n");
+}
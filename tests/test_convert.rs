@@ -0,0 +1,53 @@
+#![cfg(feature = "serde")]
+
+use oci_cfg::config::Config;
+use oci_cfg::convert::{export, import, Format, MASK};
+
+static CONFIG: &str = "# team profiles
+[DEFAULT]
+region=us-ashburn-1
+tenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx
+
+[PROD]
+user=ocid1.user.oc1..aaaaaaaaxxxxxx
+key_file=~/.oci/prod.pem
+pass_phrase=s3cr3t: \"quoted\"
+
+[EMPTY]
+";
+
+#[test]
+fn formats() {
+    let config = Config::parse(CONFIG);
+    for format in [Format::Json, Format::Toml, Format::Yaml] {
+        let masked = export(&config, format, false).unwrap();
+        assert!(masked.contains(MASK) && !masked.contains("s3cr3t"), "{}", masked);
+        let imported = import(&masked, format).unwrap();
+        assert_eq!(imported.profiles(), vec!["DEFAULT", "PROD", "EMPTY"]);
+        assert_eq!(imported.get("PROD", "pass_phrase"), None);
+
+        let included = export(&config, format, true).unwrap();
+        let imported = import(&included, format).unwrap();
+        assert_eq!(imported.get("PROD", "pass_phrase"), Some("s3cr3t: \"quoted\""));
+        assert_eq!(imported.get("DEFAULT", "tenancy"), Some("ocid1.tenancy.oc1..aaaaaaaaxxxxxx"));
+        assert_eq!(export(&imported, format, true).unwrap(), included);
+    }
+    assert!(export(&config, Format::Toml, false).unwrap().starts_with("[DEFAULT]\nregion = \"us-ashburn-1\"\n"));
+    assert_eq!("yml".parse::<Format>(), Ok(Format::Yaml));
+}
+
+#[test]
+fn invalid_documents() {
+    assert!(import("PROD:\n  region: [1, 2]\n", Format::Yaml).is_err());
+    assert!(import("{\"PROD\": {\"key\\n\": \"x\"}}", Format::Json).is_err());
+    assert!(import("{\"[PROD]\": {}}", Format::Json).is_err());
+    // keys the INI parser would read back as other keys are rejected instead of changing the profile
+    assert!(import("PROD:\n  Tenancy: ocid1.tenancy.oc1..aaaaaaaaxxxxxx\n", Format::Yaml).is_err());
+    assert!(import("PROD:\n  'a:b': x\n", Format::Yaml).is_err());
+    assert!(import("{\"PROD\": {\"region\": \" eu-frankfurt-1\"}}", Format::Json).is_err());
+    assert!(import("[PROD]\n\"#region\" = \"x\"\n", Format::Toml).is_err());
+    assert_eq!(
+        import("[PROD]\nregion = \"eu-frankfurt-1\"\n", Format::Toml).unwrap().to_string(),
+        "[PROD]\nregion=eu-frankfurt-1\n"
+    );
+}
@@ -0,0 +1,34 @@
+use oci_cfg::config::Config;
+use oci_cfg::diagnostic::check;
+
+static CONTENT: &str = "user=ocid1.user.oc1..aaaaaaaaxxxxxx
+[DEFAULT
+region us-ashburn-1
+  =aa:bb
+[ADMIN_USER] admin
+[]
+fingerprint=aa:bb
+";
+
+#[test]
+fn spans() {
+    let diagnostics = check(&Config::parse(CONTENT), Some(".oci/config"));
+    let spans: Vec<(usize, usize, usize)> = diagnostics.iter().map(|d| (d.line, d.column, d.length)).collect();
+    assert_eq!(spans, vec![(1, 1, 4), (2, 9, 1), (3, 1, 19), (4, 3, 1), (5, 13, 6), (6, 1, 2)]);
+    assert_eq!(diagnostics[1].message, "unclosed section header");
+    assert!(check(&Config::parse("[DEFAULT]\r\nregion=us-ashburn-1\r\n"), None).is_empty());
+}
+
+#[test]
+fn render() {
+    let diagnostics = check(&Config::parse(CONTENT), Some(".oci/config"));
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "error: unclosed section header
+ --> .oci/config:2:9
+  |
+2 | [DEFAULT
+  |         ^
+  = help: add ] to close the section header"
+    );
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use oci_cfg::config::Config;
+use oci_cfg::doctor::{diagnose_config, Severity};
+use oci_cfg::keys::{decode, fingerprint};
+use oci_cfg::ocid::Ocid;
+use rsa::RsaPublicKey;
+
+// writes the test key and returns a profile that uses it
+fn profile(name: &str) -> (std::path::PathBuf, String) {
+    let dir = common::temp_dir(name);
+    let key_file = dir.join("key.pem");
+    std::fs::write(&key_file, common::PRIVATE_KEY).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&key_file, std::fs::Permissions::from_mode(0o600)).unwrap();
+    }
+    let key = decode(common::PRIVATE_KEY, None).unwrap();
+    let content = format!(
+        "[DEFAULT]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\nfingerprint={}\nkey_file={}\ntenancy=ocid1.tenancy.oc1..aaaaaaaayyyyyy\nregion=us-ashburn-1\n",
+        fingerprint(&RsaPublicKey::from(&key)).unwrap(),
+        key_file.display()
+    );
+    (dir, content)
+}
+
+#[test]
+fn healthy_profile() {
+    let (dir, content) = profile("doctor_healthy");
+    let report = diagnose_config(&Config::parse(&content), "DEFAULT");
+    assert!(report.findings.is_empty(), "{:?}", report.findings);
+    assert!(report.is_healthy());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn broken_profile() {
+    let (dir, content) = profile("doctor_broken");
+    let content = format!(
+        "{}\n[BROKEN]\nuser=ocid1.user.oc2..aaaaaaaaxxxxxx\nkey_file={}\nregion=mars-north-1\n",
+        content,
+        dir.join("key.pem").display()
+    );
+    let report = diagnose_config(&Config::parse(&content), "BROKEN");
+    let checks: Vec<&str> = report.findings.iter().map(|finding| finding.check).collect();
+    assert!(checks.contains(&"inheritance"));
+    assert!(checks.contains(&"realm"));
+    assert!(checks.contains(&"region"));
+    assert!(!report.is_healthy());
+    let region = report.findings.iter().find(|finding| finding.check == "region").unwrap();
+    assert_eq!(region.key, Some("region"));
+
+    let report = diagnose_config(&Config::parse(&content.replace("fingerprint=", "fingerprint=00:")), "DEFAULT");
+    assert_eq!(report.findings[0].check, "fingerprint");
+    assert_eq!(report.findings[0].severity, Severity::Error);
+    assert_eq!(report.findings[0].key, Some("fingerprint"));
+
+    let report = diagnose_config(&Config::parse("[DEFAULT]\nkey_file=/nonexistent/key.pem\n"), "DEFAULT");
+    assert!(report.findings.iter().any(|finding| finding.check == "key-file"));
+    assert_eq!(report.findings.iter().filter(|finding| finding.check == "missing-key").count(), 4);
+    assert!(!diagnose_config(&Config::parse(""), "MISSING").is_healthy());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn ocids() {
+    let tenancy = Ocid::expect("ocid1.tenancy.oc1..aaaaaaaayyyyyy", "tenancy").unwrap();
+    assert_eq!(tenancy.realm, "oc1");
+    assert_eq!(tenancy.region, "");
+    assert!(Ocid::parse("ocid1.fingerprint.oc1..aaaaaaaaxxxxxx").is_ok());
+    assert!(Ocid::parse("aa:bb:cc").is_err());
+    assert!(Ocid::parse("ocid1.user.oc1").is_err());
+}
@@ -0,0 +1,33 @@
+use oci_cfg::config::ResolvedProfile;
+use oci_cfg::export::{export_env, Shell};
+use std::process::Command;
+
+fn profile() -> ResolvedProfile {
+    ResolvedProfile {
+        name: String::from("PROD"),
+        user: Some(String::from("ocid1.user.oc1..aaaaaaaaxxxxxx")),
+        tenancy: Some(String::from("ocid1.tenancy.oc1..aaaaaaaaxxxxxx")),
+        key_file: Some(String::from("/home/o'brien/keys/$HOME \\ `key`.pem")),
+        region: Some(String::from("eu-frankfurt-1")),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn statements() {
+    let bash = export_env(&profile(), Shell::Bash);
+    assert_eq!(bash.lines().count(), 8);
+    assert!(bash.starts_with("export OCI_CLI_USER='ocid1.user.oc1..aaaaaaaaxxxxxx'\n"));
+    assert!(bash.contains("export TF_VAR_private_key_path='/home/o'\\''brien/keys/$HOME \\ `key`.pem'\n"));
+    assert!(export_env(&profile(), Shell::Fish).contains("set -gx OCI_CLI_KEY_FILE '/home/o\\'brien/keys/$HOME \\\\ `key`.pem'\n"));
+    assert!(export_env(&profile(), Shell::PowerShell).contains("$env:TF_VAR_region = 'eu-frankfurt-1'\n"));
+    assert!(export_env(&profile(), Shell::PowerShell).contains("'/home/o''brien/keys/$HOME \\ `key`.pem'"));
+    assert_eq!("pwsh".parse::<Shell>(), Ok(Shell::PowerShell));
+}
+
+#[test]
+fn evaluated_by_sh() {
+    let script = format!("{}printf '%s' \"$OCI_CLI_KEY_FILE\"", export_env(&profile(), Shell::Bash));
+    let output = Command::new("sh").args(["-c", &script]).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "/home/o'brien/keys/$HOME \\ `key`.pem");
+}
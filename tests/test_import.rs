@@ -0,0 +1,111 @@
+mod common;
+
+use oci_cfg::config::Config;
+use oci_cfg::import::Importer;
+use oci_cfg::keys::{decode, fingerprint};
+use rsa::RsaPublicKey;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+#[test]
+fn environment_and_tfvars() {
+    let dir = common::temp_dir("import");
+    let key_file = dir.join("key.pem");
+    std::fs::write(&key_file, common::PRIVATE_KEY).unwrap();
+    let key_fingerprint = fingerprint(&RsaPublicKey::from(&decode(common::PRIVATE_KEY, None).unwrap())).unwrap();
+    let config_file = dir.join("config").to_string_lossy().to_string();
+    std::fs::write(&config_file, "# managed by hand\n[DEFAULT]\nregion=us-ashburn-1\n").unwrap();
+
+    let vars = HashMap::from([
+        ("TF_VAR_user_ocid", String::from("ocid1.user.oc1..aaaaaaaaxxxxxx")),
+        ("TF_VAR_tenancy_ocid", String::from("ocid1.tenancy.oc1..aaaaaaaaxxxxxx")),
+        ("TF_VAR_region", String::from("us-ashburn-1")),
+        ("TF_VAR_fingerprint", String::from("")),
+    ]);
+    let tfvars = format!(
+        "/* credentials of the CI user\n   rotated yearly */\nfingerprint = \"{}\" # uploaded key\nprivate_key_path = \"{}\"\nregion = \"FRA\"\ntags = {{\n  team = \"infra\"\n  env = [\"ci\"]\n}}\ninstances = 3\n",
+        key_fingerprint,
+        key_file.display()
+    );
+    let mut importer = Importer::new();
+    importer.config_file = config_file.clone();
+    importer.read_env(|name| vars.get(name).cloned());
+    importer.parse_tfvars(&tfvars).unwrap();
+    let profile = importer.import("CI").unwrap();
+
+    assert_eq!(profile.region.as_deref(), Some("eu-frankfurt-1"));
+    assert_eq!(profile.fingerprint, Some(key_fingerprint));
+    let config = Config::load(&config_file).unwrap();
+    assert_eq!(config.get("CI", "user"), Some("ocid1.user.oc1..aaaaaaaaxxxxxx"));
+    assert!(config.to_string().starts_with("# managed by hand\n[DEFAULT]\n"));
+
+    let error = importer.import("CI").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+    importer.overwrite = true;
+    importer.parse_tfvars("region = \"us-phoenix-1\"\n").unwrap();
+    importer.import("CI").unwrap();
+    let config = Config::load(&config_file).unwrap();
+    assert_eq!(config.get("CI", "region"), Some("us-phoenix-1"));
+    assert_eq!(config.profiles(), vec!["DEFAULT", "CI"]);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn rejected() {
+    let mut importer = Importer::new();
+    importer.read_env(|name| match name {
+        "TF_VAR_user_ocid" => Some(String::from("ocid1.user.oc1..aaaaaaaaxxxxxx")),
+        "TF_VAR_tenancy_ocid" => Some(String::from("ocid1.tenancy.oc2..aaaaaaaaxxxxxx")),
+        _ => None,
+    });
+    let error = importer.profile("CI").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("The key fingerprint is missing"));
+
+    let error = importer.parse_tfvars("region = \"eu-frankfurt-1\ntenancy_ocid\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 1: unterminated string");
+    let error = importer.parse_tfvars("tenancy_ocid\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 1: expected name = value");
+    let error = importer.parse_tfvars("region = \"eu-frankfurt-1\"\nfingerprint = <<EOT\naa:bb\nEOT\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 2: heredoc strings are not supported, use a quoted string");
+}
+
+#[test]
+fn pass_phrase() {
+    let dir = common::temp_dir("import_pass_phrase");
+    let key_file = dir.join("key.pem");
+    std::fs::write(&key_file, common::PRIVATE_KEY).unwrap();
+    let key_fingerprint = fingerprint(&RsaPublicKey::from(&decode(common::PRIVATE_KEY, None).unwrap())).unwrap();
+    let vars = HashMap::from([
+        ("TF_VAR_user_ocid", String::from("ocid1.user.oc1..aaaaaaaaxxxxxx")),
+        ("TF_VAR_tenancy_ocid", String::from("ocid1.tenancy.oc1..aaaaaaaaxxxxxx")),
+        ("TF_VAR_fingerprint", key_fingerprint),
+        ("TF_VAR_private_key_path", key_file.display().to_string()),
+        ("TF_VAR_region", String::from("eu-frankfurt-1")),
+        ("TF_VAR_private_key_password", String::from("s3cr3t")),
+    ]);
+    // the doctor decodes the key with the passphrase of the lookup, the process environment isn't read
+    assert!(std::env::var("TF_VAR_private_key_password").is_err());
+    let mut importer = Importer::new();
+    importer.config_file = dir.join("config").to_string_lossy().to_string();
+    importer.read_env(|name| vars.get(name).cloned());
+    std::fs::write(dir.join("terraform.tfvars"), "private_key_password = \"s3cr3t\"\n").unwrap();
+    importer.read_tfvars(&relative(&dir.join("terraform.tfvars"))).unwrap();
+    importer.import("CI").unwrap();
+    assert_eq!(importer.warnings.len(), 1);
+    assert!(importer.warnings[0].starts_with("private_key_password is not imported"));
+
+    let content = std::fs::read_to_string(dir.join("config")).unwrap();
+    assert!(!content.contains("s3cr3t"));
+    let config = Config::load(&importer.config_file).unwrap();
+    assert_eq!(config.get("CI", "pass_phrase_env"), Some("TF_VAR_private_key_password"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+// Function to return a path relative to the current directory, tfvars files are resolved against it
+fn relative(path: &Path) -> String {
+    let current_dir = std::env::current_dir().unwrap();
+    let parents = "../".repeat(current_dir.components().count() - 1);
+    format!("{}{}", parents, path.strip_prefix("/").unwrap().display())
+}
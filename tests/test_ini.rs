@@ -0,0 +1,109 @@
+#![cfg(feature = "serde")]
+
+use oci_cfg::{from_str, profile_from_str, to_string};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Tier {
+    Dev,
+    Prod,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct Profile {
+    user: Option<String>,
+    tenancy: String,
+    region: String,
+    compartment_id: Option<String>,
+    namespace: Option<String>,
+    tier: Option<Tier>,
+    retries: Option<u32>,
+    read_only: Option<bool>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[allow(non_snake_case)]
+struct Team {
+    DEFAULT: Profile,
+    dev: Option<Profile>,
+}
+
+static CONFIG: &str = "# team config
+[DEFAULT]
+tenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx
+region = us-ashburn-1
+namespace=axaxnpcrorw5
+
+[dev]
+tenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx
+region=eu-frankfurt-1
+compartment_id=ocid1.compartment.oc1..aaaaaaaayyyyyy
+tier=dev
+retries=3
+read_only=true
+";
+
+#[test]
+fn custom_structs() {
+    let team: Team = from_str(CONFIG).unwrap();
+    let dev = team.dev.as_ref().unwrap();
+    assert_eq!(team.DEFAULT.region, "us-ashburn-1");
+    assert_eq!(dev.compartment_id.as_deref(), Some("ocid1.compartment.oc1..aaaaaaaayyyyyy"));
+    assert_eq!((dev.tier.as_ref(), dev.retries, dev.read_only), (Some(&Tier::Dev), Some(3), Some(true)));
+    assert_eq!(dev.namespace, None);
+
+    let written = to_string(&team).unwrap();
+    assert!(written.starts_with("[DEFAULT]\ntenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx\nregion=us-ashburn-1\nnamespace=axaxnpcrorw5\n\n[dev]\n"));
+    assert!(written.ends_with("tier=dev\nretries=3\nread_only=true\n"));
+    assert_eq!(from_str::<Team>(&written).unwrap(), team);
+
+    let profiles: BTreeMap<String, BTreeMap<String, String>> = from_str(CONFIG).unwrap();
+    assert_eq!(profiles["dev"]["retries"], "3");
+}
+
+#[test]
+fn single_profile() {
+    let dev: Profile = profile_from_str(CONFIG, "dev").unwrap();
+    assert_eq!(dev.namespace.as_deref(), Some("axaxnpcrorw5"));
+    assert_eq!(dev.region, "eu-frankfurt-1");
+    let error = profile_from_str::<Profile>(CONFIG, "prod").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn invalid_values() {
+    let error = profile_from_str::<Profile>(&CONFIG.replace("retries=3", "retries=many"), "dev").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("many"));
+    assert!(profile_from_str::<Profile>("[dev]\nregion=eu-frankfurt-1\n", "dev").unwrap_err().to_string().contains("tenancy"));
+
+    assert!(to_string(&vec!["DEFAULT"]).is_err());
+    assert!(to_string(&BTreeMap::from([("DEFAULT", vec!["a"])])).is_err());
+    assert!(to_string(&BTreeMap::from([("DEFAULT", BTreeMap::from([("region", vec!["a"])]))])).is_err());
+    assert!(to_string(&BTreeMap::from([("DEFAULT", BTreeMap::from([("region", "a\nb")]))])).is_err());
+}
+
+#[test]
+fn round_trip() {
+    // entries the parser would read back differently are rejected
+    for (key, value) in [("a:b", "x"), ("Region", "x"), ("#region", "x"), (";region", "x"), ("[region", "x"), (" region", "x"), ("region", " x"), ("region", "x ")] {
+        let profiles = BTreeMap::from([("DEFAULT", BTreeMap::from([(key, value)]))]);
+        assert!(to_string(&profiles).is_err(), "{:?}={:?} was written", key, value);
+    }
+    assert!(to_string(&BTreeMap::from([(" DEV", BTreeMap::from([("region", "x")]))])).is_err());
+
+    // everything that is written reads back unchanged
+    let profiles = BTreeMap::from([(
+        String::from("DEV"),
+        BTreeMap::from([
+            (String::from("region"), String::from("eu-frankfurt-1")),
+            (String::from("url"), String::from("https://a=b:c # d")),
+            (String::from("empty"), String::new()),
+        ]),
+    )]);
+    let written = to_string(&profiles).unwrap();
+    assert_eq!(from_str::<BTreeMap<String, BTreeMap<String, String>>>(&written).unwrap(), profiles);
+}
@@ -0,0 +1,55 @@
+use oci_cfg::config::Config;
+use oci_cfg::lint::{fix, lint, BOM, COLON_SEPARATOR, CRLF, DUPLICATE_KEY, DUPLICATE_SECTION, FOREIGN_HOME, TRAILING_WHITESPACE, UNKNOWN_KEY};
+
+static CONTENT: &str = "\u{feff}[DEFAULT]\r
+# tenancy of the account\r
+tenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx\r
+region : us-ashburn-1\r
+\r
+[ADMIN_USER]\r
+user=ocid1.user.oc1..aaaaaaaaxxxxxx   \r
+fingerprnt=aa:bb\r
+key_file=/home/somebody-else/.oci/oci_api_key.pem\r
+\r
+[DEFAULT]\r
+region=eu-frankfurt-1\r
+";
+
+#[test]
+fn rules() {
+    let lints = lint(&Config::parse(CONTENT));
+    let found = |rule: &str| lints.iter().filter(|lint| lint.rule == rule).map(|lint| lint.line).collect::<Vec<usize>>();
+    assert_eq!(found(BOM), vec![1]);
+    assert_eq!(found(CRLF).len(), 12);
+    assert_eq!(found(COLON_SEPARATOR), vec![4]);
+    assert_eq!(found(TRAILING_WHITESPACE), vec![7]);
+    assert_eq!(found(UNKNOWN_KEY), vec![8]);
+    assert_eq!(found(FOREIGN_HOME), vec![9]);
+    assert_eq!(found(DUPLICATE_SECTION), vec![11]);
+    assert_eq!(found(DUPLICATE_KEY), vec![12]);
+    assert!(lints.iter().any(|lint| lint.message.contains("did you mean fingerprint")));
+}
+
+#[test]
+fn fixes() {
+    let mut config = Config::parse(CONTENT);
+    let fixed = fix(&mut config);
+    assert!(fixed.iter().all(|lint| lint.fixable));
+    assert_eq!(
+        config.to_string(),
+        "[DEFAULT]
+# tenancy of the account
+tenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx
+region=eu-frankfurt-1
+
+[ADMIN_USER]
+user=ocid1.user.oc1..aaaaaaaaxxxxxx
+fingerprnt=aa:bb
+key_file=/home/somebody-else/.oci/oci_api_key.pem
+
+"
+    );
+    let remaining: Vec<&str> = lint(&config).iter().map(|lint| lint.rule).collect();
+    assert_eq!(remaining, vec![UNKNOWN_KEY, FOREIGN_HOME]);
+    assert_eq!(config.profile("DEFAULT").unwrap().region.as_deref(), Some("eu-frankfurt-1"));
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::temp_dir;
+use oci_cfg::config::Config;
+use oci_cfg::keys::{generate, KeySize, PRIVATE_KEY};
+use oci_cfg::passphrase::PassPhraseSource;
+
+#[test]
+fn sources() {
+    let dir = temp_dir("passphrase");
+    std::fs::write(dir.join("pass_phrase"), "from file\n").unwrap();
+    std::env::set_var("OCI_CFG_TEST_PASS_PHRASE", "from env");
+
+    let env = PassPhraseSource::Env(String::from("OCI_CFG_TEST_PASS_PHRASE"));
+    let file = PassPhraseSource::File(dir.join("pass_phrase").display().to_string());
+    let command = PassPhraseSource::Command(String::from("echo from command"));
+    assert_eq!(env.resolve().unwrap().expose(), "from env");
+    assert_eq!(file.resolve().unwrap().expose(), "from file");
+    assert_eq!(command.resolve().unwrap().expose(), "from command");
+    assert!(PassPhraseSource::Command(String::from("exit 1")).resolve().is_err());
+    assert!(PassPhraseSource::Env(String::from("OCI_CFG_TEST_UNSET")).resolve().is_err());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn referenced_pass_phrase() {
+    let dir = temp_dir("passphrase_profile");
+    generate(dir.to_str().unwrap(), KeySize::Rsa2048, Some("from command"), false).unwrap();
+    let key_file = dir.join(PRIVATE_KEY).display().to_string();
+
+    let mut config = Config::parse(&format!("[DEFAULT]\nkey_file={}\npass_phrase=plain\n", key_file));
+    config.set_pass_phrase_source("DEFAULT", &PassPhraseSource::Command(String::from("printf 'from command'")));
+    assert_eq!(
+        config.to_string(),
+        format!("[DEFAULT]\nkey_file={}\npass_phrase_command=printf 'from command'\n", key_file)
+    );
+
+    let profile = config.profile("DEFAULT").unwrap();
+    assert!(profile.pass_phrase.is_none());
+    assert!(profile.signing_key().is_ok());
+
+    // the command runs when the key is decoded, loading the profile doesn't run it
+    let marker = dir.join("marker");
+    let command = format!("touch {} && printf 'from command'", marker.display());
+    config.set_pass_phrase_source("DEFAULT", &PassPhraseSource::Command(command));
+    let profile = config.profile("DEFAULT").unwrap();
+    assert!(!marker.exists());
+    assert!(profile.signing_key().is_ok());
+    assert!(marker.exists());
+
+    config.set_pass_phrase_source("DEFAULT", &PassPhraseSource::Command(String::from("exit 1")));
+    let error = config.profile("DEFAULT").unwrap().signing_key().unwrap_err();
+    assert!(error.to_string().starts_with("The passphrase of profile DEFAULT can't be resolved"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn edit_profiles() {
+    let mut config = Config::parse("# comment\n[DEFAULT]\nregion: us-ashburn-1\n");
+    config.set("DEFAULT", "tenancy", "ocid1.tenancy.oc1..aaaaaaaaxxxxxx");
+    config.set("DEFAULT", "region", "eu-frankfurt-1");
+    config.set("ADMIN_USER", "user", "ocid1.user.oc1..aaaaaaaaxxxxxx");
+    assert_eq!(
+        config.to_string(),
+        "# comment\n[DEFAULT]\nregion: eu-frankfurt-1\ntenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx\n\n[ADMIN_USER]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\n"
+    );
+    assert!(config.remove("DEFAULT", "tenancy"));
+    assert!(!config.remove("DEFAULT", "tenancy"));
+}
@@ -0,0 +1,42 @@
+#![cfg(unix)]
+mod common;
+
+use common::{temp_dir, PRIVATE_KEY};
+use oci_cfg::file::{check_permissions, create, repair_permissions, PermissionIssue};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+fn mode(path: &std::path::Path) -> u32 {
+    fs::metadata(path).unwrap().permissions().mode() & 0o777
+}
+
+#[test]
+fn create_private() {
+    let dir = temp_dir("permissions_create").join(".oci");
+    let config_file = create(dir.to_str().unwrap(), "config");
+    assert_eq!(mode(&dir), 0o700);
+    assert_eq!(mode(std::path::Path::new(&config_file)), 0o600);
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn repair() {
+    let dir = temp_dir("permissions_repair");
+    let key_file = dir.join("key.pem");
+    fs::write(&key_file, PRIVATE_KEY).unwrap();
+    fs::write(dir.join("config"), format!("[DEFAULT]\nkey_file={}\n", key_file.display())).unwrap();
+    for path in [dir.clone(), dir.join("config"), key_file.clone()] {
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let issues = check_permissions(dir.to_str().unwrap(), "config").unwrap();
+    assert_eq!(issues.len(), 3);
+    assert!(issues.contains(&PermissionIssue::Insecure { path: key_file.clone(), mode: 0o755, expected: 0o600 }));
+
+    let repaired = repair_permissions(dir.to_str().unwrap(), "config").unwrap();
+    assert_eq!(repaired.len(), 3);
+    assert_eq!(mode(&dir), 0o700);
+    assert_eq!(mode(&key_file), 0o600);
+    assert!(check_permissions(dir.to_str().unwrap(), "config").unwrap().is_empty());
+    fs::remove_dir_all(dir).unwrap();
+}
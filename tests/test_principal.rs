@@ -0,0 +1,122 @@
+mod common;
+
+use common::{certificate, metadata_stub, temp_dir, token, PRIVATE_KEY};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use oci_cfg::principal::{InstancePrincipal, ResourcePrincipal, Source, WorkloadIdentity};
+use oci_cfg::signer::Signer;
+use std::collections::HashMap;
+
+static RPST_CLAIMS: &str = r#""res_tenant":"ocid1.tenancy.oc1..aaaaaaaaxxxxxx","res_type":"fnfunc""#;
+
+#[test]
+fn resource_principal() {
+    let rpst = token(RPST_CLAIMS, 3600);
+    let vars: HashMap<&str, String> = HashMap::from([
+        ("OCI_RESOURCE_PRINCIPAL_VERSION", String::from("2.2")),
+        ("OCI_RESOURCE_PRINCIPAL_RPST", rpst.clone()),
+        ("OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM", String::from(PRIVATE_KEY)),
+        ("OCI_RESOURCE_PRINCIPAL_REGION", String::from("us-phoenix-1")),
+    ]);
+    let principal = ResourcePrincipal::from_vars(|name| vars.get(name).cloned()).unwrap();
+    let profile = principal.profile().unwrap();
+    assert_eq!(profile.tenancy.as_deref(), Some("ocid1.tenancy.oc1..aaaaaaaaxxxxxx"));
+    assert_eq!(profile.region.as_deref(), Some("us-phoenix-1"));
+
+    let signer = Signer::from_profile(&profile).unwrap();
+    assert_eq!(signer.key_id(), format!("ST${}", rpst));
+}
+
+#[test]
+fn resource_principal_files() {
+    let dir = temp_dir("principal");
+    std::fs::write(dir.join("rpst"), token(RPST_CLAIMS, 3600)).unwrap();
+    std::fs::write(dir.join("private.pem"), PRIVATE_KEY).unwrap();
+    let vars: HashMap<&str, String> = HashMap::from([
+        ("OCI_RESOURCE_PRINCIPAL_VERSION", String::from("2.2")),
+        ("OCI_RESOURCE_PRINCIPAL_RPST", dir.join("rpst").display().to_string()),
+        ("OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM", dir.join("private.pem").display().to_string()),
+        ("OCI_RESOURCE_PRINCIPAL_REGION", String::from("us-phoenix-1")),
+    ]);
+    let principal = ResourcePrincipal::from_vars(|name| vars.get(name).cloned()).unwrap();
+    assert!(matches!(principal.token, Source::File(_)));
+    let profile = principal.profile().unwrap();
+    assert_eq!(profile.key_file, Some(dir.join("private.pem").display().to_string()));
+    assert!(Signer::from_profile(&profile).unwrap().key_id().starts_with("ST$"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unsupported_version() {
+    let principal = ResourcePrincipal::from_vars(|name| match name {
+        "OCI_RESOURCE_PRINCIPAL_VERSION" => Some(String::from("1.1")),
+        _ => None,
+    });
+    assert_eq!(principal.unwrap_err().kind(), std::io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn workload_identity() {
+    let identity = WorkloadIdentity::from_vars(|name| match name {
+        "OCI_RESOURCE_PRINCIPAL_REGION" => Some(String::from("eu-frankfurt-1")),
+        "KUBERNETES_SERVICE_HOST" => Some(String::from("10.96.0.1")),
+        _ => None,
+    })
+    .unwrap();
+    assert_eq!(identity.token_file, "/var/run/secrets/kubernetes.io/serviceaccount/token");
+    assert!(WorkloadIdentity::from_vars(|_| None).is_err());
+}
+
+#[test]
+fn workload_identity_exchange() {
+    let dir = temp_dir("workload_identity");
+    std::fs::write(dir.join("token"), "service-account-token\n").unwrap();
+    let vars: HashMap<&str, String> = HashMap::from([
+        ("OCI_RESOURCE_PRINCIPAL_REGION", String::from("eu-frankfurt-1")),
+        ("KUBERNETES_SERVICE_HOST", String::from("10.96.0.1")),
+        ("OCI_KUBERNETES_SERVICE_ACCOUNT_TOKEN_PATH", dir.join("token").display().to_string()),
+    ]);
+    let identity = WorkloadIdentity::from_vars(|name| vars.get(name).cloned()).unwrap();
+
+    let rpst = token(RPST_CLAIMS, 3600);
+    let response = STANDARD.encode(format!(r#"{{"token":"ST${}"}}"#, rpst));
+    let transport: oci_cfg::auth::Transport = Box::new(move |url: &str, headers: &[(String, String)], body: &str| {
+        assert_eq!(url, "https://10.96.0.1:12250/resourcePrincipalSessionTokens");
+        assert!(headers.contains(&(String::from("authorization"), String::from("Bearer service-account-token"))));
+        assert!(body.contains("\"podKey\""));
+        Ok(response.clone())
+    });
+    let profile = identity.profile(&transport).unwrap();
+    assert_eq!(profile.tenancy.as_deref(), Some("ocid1.tenancy.oc1..aaaaaaaaxxxxxx"));
+    assert_eq!(profile.region.as_deref(), Some("eu-frankfurt-1"));
+    assert_eq!(Signer::from_profile(&profile).unwrap().key_id(), format!("ST${}", rpst));
+
+    let failing: oci_cfg::auth::Transport = Box::new(|_: &str, _: &[(String, String)], _: &str| Ok(String::from("not base64")));
+    assert_eq!(identity.profile(&failing).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn instance_principal() {
+    let url = metadata_stub(certificate("ocid1.tenancy.oc1..aaaaaaaaxxxxxx"));
+    let principal = InstancePrincipal::from_url(&url).unwrap();
+    assert_eq!(principal.region, "us-phoenix-1");
+    assert_eq!(principal.tenancy, "ocid1.tenancy.oc1..aaaaaaaaxxxxxx");
+    assert_eq!(principal.private_key.expose(), PRIVATE_KEY);
+
+    let key_id = principal.key_id().unwrap();
+    assert!(key_id.starts_with("ocid1.tenancy.oc1..aaaaaaaaxxxxxx/fed-x509/"));
+    let profile = principal.profile().unwrap();
+    assert!(profile.signing_key().is_ok());
+    assert!(InstancePrincipal::from_url("http://127.0.0.1:1/opc/v2").is_err());
+}
+
+#[test]
+fn malformed_certificate() {
+    // the length in front of the tenant is shorter than the prefix, or points past the end of the certificate
+    for der in [b"\x0c\x03opc-tenant:ocid1".to_vec(), b"\x0c\x7fopc-tenant:ocid1".to_vec(), b"opc-tenant:".to_vec()] {
+        let pem = format!("-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n", STANDARD.encode(der));
+        let error = InstancePrincipal::from_url(&metadata_stub(pem)).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
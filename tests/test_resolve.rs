@@ -0,0 +1,82 @@
+use oci_cfg::cli_rc::CliRc;
+use oci_cfg::config::Config;
+use oci_cfg::resolve::{Layer, Resolver};
+
+static CONTENT: &str = "[DEFAULT]
+user=ocid1.user.oc1..aaaaaaaadefault
+region=eu-frankfurt-1
+tenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx
+
+[ADMIN_USER]
+user=ocid1.user.oc1..aaaaaaaaadmin
+pass_phrase=secret
+";
+
+#[test]
+fn layers() {
+    let config = Config::parse(CONTENT);
+    let vars = |name: &str| match name {
+        "OCI_CLI_PROFILE" => Some(String::from("ADMIN_USER")),
+        "OCI_CLI_FINGERPRINT" => Some(String::from("aa:bb")),
+        _ => None,
+    };
+    let mut resolver = Resolver::new();
+    resolver.config_file = String::from(".oci/config");
+    resolver.set("key_file", "~/.oci/admin.pem");
+    let resolution = resolver.resolve_config(&config, vars).unwrap();
+
+    assert_eq!(resolution.profile, "ADMIN_USER");
+    assert_eq!(resolution.profile_origin.layer, Layer::Environment);
+    assert_eq!(resolution.origin("key_file").unwrap().layer, Layer::Explicit);
+    assert_eq!(resolution.origin("fingerprint").unwrap().to_string(), "environment variable OCI_CLI_FINGERPRINT");
+    assert_eq!(resolution.get("user"), Some("ocid1.user.oc1..aaaaaaaaadmin"));
+    assert_eq!(resolution.origin("user").unwrap().to_string(), "profile at .oci/config:7");
+    assert_eq!(resolution.origin("region").unwrap().to_string(), "DEFAULT profile at .oci/config:3");
+    assert!(resolution.get("pass_phrase").is_none());
+
+    let profile = resolution.profile();
+    assert_eq!(profile.fingerprint.as_deref(), Some("aa:bb"));
+    assert_eq!(profile.pass_phrase.unwrap().expose(), "secret");
+}
+
+#[test]
+fn selection() {
+    let config = Config::parse(CONTENT);
+    let resolution = Resolver::new().resolve_config(&config, |_| None).unwrap();
+    assert_eq!(resolution.profile, "DEFAULT");
+    assert_eq!(resolution.get("user"), Some("ocid1.user.oc1..aaaaaaaadefault"));
+
+    let mut resolver = Resolver::new();
+    resolver.profile = Some(String::from("MISSING"));
+    assert!(resolver.resolve_config(&config, |_| None).is_err());
+    assert!(Resolver::new().resolve_config(&config, |_| Some(String::from("MISSING"))).is_err());
+}
+
+#[test]
+fn default_profile() {
+    let config = Config::parse(CONTENT);
+    let mut resolver = Resolver::new();
+    resolver.cli_rc = Some(CliRc::parse("[OCI_CLI_SETTINGS]\ndefault_profile=ADMIN_USER\n"));
+    let resolution = resolver.resolve_config(&config, |_| None).unwrap();
+    assert_eq!(resolution.profile, "ADMIN_USER");
+    assert_eq!(resolution.profile_origin.to_string(), "default_profile at .oci/oci_cli_rc:2");
+
+    let resolution = resolver.resolve_config(&config, |_| Some(String::from("DEFAULT"))).unwrap();
+    assert_eq!(resolution.profile_origin.layer, Layer::Environment);
+}
+
+#[test]
+fn config_file() {
+    let vars = |path: &'static str| move |name: &str| (name == "OCI_CLI_CONFIG_FILE").then(|| path.to_string());
+    let mut resolver = Resolver::new();
+    assert_eq!(resolver.config_file_with(|_| None).unwrap(), ".oci/config");
+    assert_eq!(resolver.config_file_with(vars("")).unwrap(), ".oci/config");
+    assert_eq!(resolver.config_file_with(vars("/etc/oci/config")).unwrap(), "/etc/oci/config");
+    assert_eq!(resolver.config_file_with(vars("~/.oci/ci")).unwrap(), ".oci/ci");
+    let relative = std::env::current_dir().unwrap().join("oci/config");
+    assert_eq!(resolver.config_file_with(vars("oci/config")).unwrap(), relative.to_string_lossy());
+
+    // a config file set on the resolver takes precedence over the environment
+    resolver.config_file = String::from("/srv/oci/config");
+    assert_eq!(resolver.config_file_with(vars("/etc/oci/config")).unwrap(), "/srv/oci/config");
+}
@@ -0,0 +1,29 @@
+use oci_cfg::config::Config;
+use oci_cfg::secret::Secret;
+
+#[test]
+fn redacted() {
+    let secret = Secret::from("passphrase");
+    assert_eq!(secret.to_string(), "********");
+    assert!(!format!("{:?}", secret).contains("passphrase"));
+    assert_eq!(secret.expose(), "passphrase");
+}
+
+#[test]
+fn redacted_profile() {
+    let config = Config::parse("[DEFAULT]\nuser=ocid1.user.oc1..aaaaaaaaxxxxxx\npass_phrase=passphrase\n");
+    let profile = config.profile("DEFAULT").unwrap();
+    assert!(!format!("{:?}", profile).contains("passphrase"));
+    assert_eq!(profile.pass_phrase.unwrap().expose(), "passphrase");
+}
+
+#[test]
+fn edited() {
+    let mut secret = Secret::default();
+    for c in "passphrase!".chars() {
+        secret.push(c);
+    }
+    assert_eq!(secret.pop(), Some('!'));
+    assert_eq!(secret.expose(), "passphrase");
+    assert_eq!(secret.to_string(), "********");
+}
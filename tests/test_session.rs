@@ -0,0 +1,49 @@
+mod common;
+
+use common::{temp_dir, token};
+use oci_cfg::config::Config;
+use oci_cfg::keys::{generate, KeySize, PRIVATE_KEY};
+use oci_cfg::session::{SessionToken, Status, EXPIRY_WARNING};
+use oci_cfg::signer::Signer;
+use std::time::Duration;
+
+// claims of the user the tokens are issued to
+static CLAIMS: &str = r#""sub":"ocid1.user.oc1..aaaaaaaaxxxxxx""#;
+
+#[test]
+fn token_status() {
+    let valid = SessionToken::parse(&token(CLAIMS, 3600)).unwrap();
+    assert!(matches!(valid.status(EXPIRY_WARNING), Status::Valid(remaining) if remaining > Duration::from_secs(3500)));
+    assert!(valid.key_id().starts_with("ST$eyJ"));
+
+    let expiring = SessionToken::parse(&token(CLAIMS, 60)).unwrap();
+    assert!(matches!(expiring.status(EXPIRY_WARNING), Status::Expiring(_)));
+
+    let expired = SessionToken::parse(&token(CLAIMS, -60)).unwrap();
+    assert_eq!(expired.status(EXPIRY_WARNING), Status::Expired);
+    assert!(expired.remaining().is_none());
+
+    assert!(SessionToken::parse("not a token").is_err());
+}
+
+#[test]
+fn session_profile() {
+    let dir = temp_dir("session");
+    generate(dir.to_str().unwrap(), KeySize::Rsa2048, None, false).unwrap();
+    let session_token = token(CLAIMS, 3600);
+    std::fs::write(dir.join("token"), &session_token).unwrap();
+
+    let config = Config::parse(&format!(
+        "[SESSION]\nfingerprint=aa:bb\nkey_file={}\ntenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx\nregion=us-ashburn-1\nsecurity_token_file={}\n",
+        dir.join(PRIVATE_KEY).display(),
+        dir.join("token").display()
+    ));
+    let profile = config.profile("SESSION").unwrap();
+    assert!(profile.is_session());
+    let signer = Signer::from_profile(&profile).unwrap();
+    assert_eq!(signer.key_id(), format!("ST${}", session_token));
+
+    std::fs::write(dir.join("token"), token(CLAIMS, -60)).unwrap();
+    assert!(Signer::from_profile(&profile).is_err());
+    std::fs::remove_dir_all(dir).unwrap();
+}
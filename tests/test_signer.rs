@@ -0,0 +1,62 @@
+mod common;
+
+use common::PRIVATE_KEY;
+use oci_cfg::keys::decode;
+use oci_cfg::signer::Signer;
+
+// key id and requests published in the OCI documentation on request signatures
+static KEY_ID: &str = "ocid1.tenancy.oc1..aaaaaaaaba3pv6wkcr4jqae5f15p2b2m2yt2j6rx32uzr4h25vqstifsfdsq/ocid1.user.oc1..aaaaaaaat5nvwcna5j6aqzjcaty5eqbb6qt2jvpkanghtgdaqedqw3rynjq/20:3b:97:13:55:1c:5b:0d:d3:37:d8:50:4e:c5:3a:34";
+
+fn signer() -> Signer {
+    Signer::new(KEY_ID, decode(PRIVATE_KEY, None).unwrap())
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> &'a str {
+    headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str()).unwrap()
+}
+
+#[test]
+fn get_request() {
+    let mut headers = vec![(String::from("date"), String::from("Thu, 05 Jan 2014 21:31:40 GMT"))];
+    signer()
+        .sign(
+            "GET",
+            "https://iaas.us-phoenix-1.oraclecloud.com/20160918/instances?availabilityDomain=Pjwf%3A%20PHX-AD-1&compartmentId=ocid1.compartment.oc1..aaaaaaaam3we6vgnherjq5q2idnccdflvjsnog7mlr6rtdb25gilchfeyjxa&displayName=TeamXInstances&volumeId=ocid1.volume.oc1.phx.abyhqljrgvttnlx73nmrwfaux7kcvzfs3s66izvxf2h4lgvyndsdsnoiwr5q",
+            &mut headers,
+            b"",
+        )
+        .unwrap();
+    assert_eq!(
+        header(&headers, "authorization"),
+        format!(
+            "Signature version=\"1\",keyId=\"{}\",algorithm=\"rsa-sha256\",headers=\"date (request-target) host\",signature=\"{}\"",
+            KEY_ID,
+            "GBas7grhyrhSKHP6AVIj/h5/Vp8bd/peM79H9Wv8kjoaCivujVXlpbKLjMPeDUhxkFIWtTtLBj3sUzaFj34XE6YZAHc9r2DmE4pMwOAy/kiITcZxa1oHPOeRheC0jP2dqbTll8fmTZVwKZOKHYPtrLJIJQHJjNvxFWeHQjMaR7M="
+        )
+    );
+    assert_eq!(header(&headers, "host"), "iaas.us-phoenix-1.oraclecloud.com");
+}
+
+#[test]
+fn post_request() {
+    let body = r#"{
+    "compartmentId": "ocid1.compartment.oc1..aaaaaaaam3we6vgnherjq5q2idnccdflvjsnog7mlr6rtdb25gilchfeyjxa",
+    "instanceId": "ocid1.instance.oc1.phx.abuw4ljrlsfiqw6vzzxb43vyypt4pkodawglp3wqxjqofakrwvou52gb6s5a",
+    "volumeId": "ocid1.volume.oc1.phx.abyhqljrgvttnlx73nmrwfaux7kcvzfs3s66izvxf2h4lgvyndsdsnoiwr5q"
+}"#;
+    let mut headers = vec![(String::from("date"), String::from("Thu, 05 Jan 2014 21:31:40 GMT"))];
+    signer()
+        .sign("POST", "https://iaas.us-phoenix-1.oraclecloud.com/20160918/volumeAttachments", &mut headers, body.as_bytes())
+        .unwrap();
+    assert_eq!(header(&headers, "content-length"), "316");
+    assert_eq!(header(&headers, "content-type"), "application/json");
+    assert_eq!(header(&headers, "x-content-sha256"), "V9Z20UJTvkvpJ50flBzKE32+6m2zJjweHpDMX/U4Uy0=");
+    assert_eq!(
+        header(&headers, "authorization"),
+        format!(
+            "Signature version=\"1\",keyId=\"{}\",algorithm=\"rsa-sha256\",headers=\"date (request-target) host content-length content-type x-content-sha256\",signature=\"{}\"",
+            KEY_ID,
+            "Mje8vIDPlwIHmD/cTDwRxE7HaAvBg16JnVcsuqaNRim23fFPgQfLoOOxae6WqKb1uPjYEl0qIdazWaBy/Ml8DRhqlocMwoSXv0fbukP8J5N80LCmzT/FFBvIvTB91XuXI3hYfP9Zt1l7S6ieVadHUfqBedWH0itrtPJBgKmrWso="
+        )
+    );
+}
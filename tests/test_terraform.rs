@@ -0,0 +1,61 @@
+use oci_cfg::config::ResolvedProfile;
+use oci_cfg::terraform::{declarations, provider, tfvars, Auth, Provider};
+
+fn api_key() -> ResolvedProfile {
+    ResolvedProfile {
+        name: String::from("PROD"),
+        user: Some(String::from("ocid1.user.oc1..aaaaaaaaxxxxxx")),
+        fingerprint: Some(String::from("20:3b:97:13:55:1c:5b:0d:d3:37:d8:50:4e:c5:3a:34")),
+        key_file: Some(String::from("C:\\keys\\\"oci\".pem")),
+        tenancy: Some(String::from("ocid1.tenancy.oc1..aaaaaaaaxxxxxx")),
+        region: Some(String::from("eu-frankfurt-1")),
+        ..Default::default()
+    }
+}
+
+fn session() -> ResolvedProfile {
+    ResolvedProfile {
+        name: String::from("SESSION"),
+        tenancy: Some(String::from("ocid1.tenancy.oc1..aaaaaaaaxxxxxx")),
+        region: Some(String::from("us-ashburn-1")),
+        key_file: Some(String::from("/home/opc/.oci/sessions/SESSION/oci_api_key.pem")),
+        security_token_file: Some(String::from("/home/opc/.oci/sessions/SESSION/token")),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn api_key_provider() {
+    assert_eq!(Auth::of(&api_key()), Auth::ApiKey);
+    assert_eq!(
+        provider(&api_key(), Provider::ConfigFile).unwrap(),
+        "provider \"oci\" {\n  auth                = \"APIKey\"\n  config_file_profile = \"PROD\"\n  region              = var.region\n}\n"
+    );
+    assert_eq!(
+        provider(&api_key(), Provider::Variables).unwrap(),
+        "provider \"oci\" {\n  user_ocid        = var.user_ocid\n  fingerprint      = var.fingerprint\n  private_key_path = var.private_key_path\n  tenancy_ocid     = var.tenancy_ocid\n  region           = var.region\n}\n"
+    );
+    assert_eq!(declarations(&api_key()).lines().count(), 5);
+    assert!(declarations(&api_key()).contains("variable \"private_key_path\" {}\n"));
+
+    let tfvars = tfvars(&api_key());
+    assert_eq!(tfvars.lines().count(), 5);
+    assert!(tfvars.starts_with("user_ocid        = \"ocid1.user.oc1..aaaaaaaaxxxxxx\"\n"));
+    assert!(tfvars.contains("private_key_path = \"C:\\\\keys\\\\\\\"oci\\\".pem\"\n"));
+    assert!(tfvars.ends_with("region           = \"eu-frankfurt-1\"\n"));
+}
+
+#[test]
+fn security_token_provider() {
+    assert_eq!(Auth::of(&session()), Auth::SecurityToken);
+    assert_eq!(
+        provider(&session(), Provider::ConfigFile).unwrap(),
+        "provider \"oci\" {\n  auth                = \"SecurityToken\"\n  config_file_profile = \"SESSION\"\n  region              = var.region\n}\n"
+    );
+    assert!(provider(&session(), Provider::Variables).is_err());
+    assert_eq!(declarations(&session()), "variable \"tenancy_ocid\" {}\nvariable \"region\" {}\n");
+    assert_eq!(
+        tfvars(&session()),
+        "tenancy_ocid = \"ocid1.tenancy.oc1..aaaaaaaaxxxxxx\"\nregion       = \"us-ashburn-1\"\n"
+    );
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "derive")]
+
+mod common;
+
+use oci_cfg::typed::OciProfile;
+use oci_cfg::OciProfile;
+use serde::Deserialize;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize, OciProfile)]
+struct Profile {
+    #[oci(ocid = "tenancy")]
+    tenancy: String,
+    #[oci(region)]
+    region: String,
+    #[oci(ocid = "compartment")]
+    compartment_id: Option<String>,
+    namespace: Option<String>,
+    #[oci(path, must_exist)]
+    key_file: PathBuf,
+    #[oci(path)]
+    log_file: Option<String>,
+}
+
+#[test]
+fn default_aware_loading() {
+    let dir = common::temp_dir("typed");
+    let key_file = dir.join("key.pem");
+    std::fs::write(&key_file, common::PRIVATE_KEY).unwrap();
+    let config_file = dir.join("config");
+    let config = format!(
+        "[DEFAULT]\ntenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx\nregion=us-ashburn-1\nkey_file={}\n\n[DEV]\nregion=eu-frankfurt-1\ncompartment_id=ocid1.compartment.oc1..aaaaaaaayyyyyy\nnamespace=axaxnpcrorw5\n",
+        key_file.display()
+    );
+    std::fs::write(&config_file, &config).unwrap();
+
+    let dev = Profile::load_from(config_file.to_str().unwrap(), "DEV").unwrap();
+    assert_eq!(dev.tenancy, "ocid1.tenancy.oc1..aaaaaaaaxxxxxx");
+    assert_eq!(dev.region, "eu-frankfurt-1");
+    assert_eq!(dev.namespace.as_deref(), Some("axaxnpcrorw5"));
+    assert_eq!(dev.key_file, key_file);
+    assert_eq!(dev.log_file, None);
+    let default = Profile::from_config(&config, "DEFAULT").unwrap();
+    assert_eq!(default.compartment_id, None);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn validation() {
+    let valid = "tenancy=ocid1.tenancy.oc1..aaaaaaaaxxxxxx\nregion=eu-frankfurt-1\nkey_file=/\n";
+    assert!(Profile::from_config(&format!("[DEV]\n{}", valid), "DEV").is_ok());
+
+    let error = Profile::from_config(&format!("[DEV]\n{}compartment_id=ocid1.user.oc1..aaaaaaaaxxxxxx\n", valid), "DEV").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().starts_with("compartment_id: Invalid OCID"));
+
+    let error = Profile::from_config(&format!("[DEV]\n{}", valid.replace("eu-frankfurt-1", "eu-atlantis-1")), "DEV").unwrap_err();
+    assert!(error.to_string().starts_with("region: The region eu-atlantis-1 is unknown"));
+
+    let error = Profile::from_config(&format!("[DEV]\n{}", valid.replace("key_file=/", "key_file=/nonexistent.pem")), "DEV").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+
+    let error = Profile::from_config(&format!("[DEV]\n{}log_file= \n", valid), "DEV").unwrap_err();
+    assert_eq!(error.to_string(), "log_file: The path is empty");
+}
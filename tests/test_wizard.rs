@@ -0,0 +1,83 @@
+mod common;
+
+use oci_cfg::config::Config;
+use oci_cfg::keys::{decode, fingerprint, PRIVATE_KEY};
+use oci_cfg::wizard::Wizard;
+use rsa::RsaPublicKey;
+
+#[test]
+fn scripted_setup() {
+    let dir = common::temp_dir("wizard");
+    let key_file = dir.join("key.pem");
+    std::fs::write(&key_file, common::PRIVATE_KEY).unwrap();
+    let config_file = dir.join("config");
+    std::fs::write(&config_file, "[DEFAULT]\nregion=us-ashburn-1\n").unwrap();
+
+    let answers = format!(
+        "DEFAULT\nADMIN\nbad\nocid1.user.oc1..aaaaaaaaxxxxxx\nocid1.tenancy.oc2..aaaaaaaayyyyyy\nocid1.tenancy.oc1..aaaaaaaayyyyyy\neu-\neu-fr\nn\n\n/nonexistent.pem\n{}\n",
+        key_file.display()
+    );
+    let mut output: Vec<u8> = Vec::new();
+    let mut wizard = Wizard::new(answers.as_bytes(), &mut output);
+    wizard.config_file = config_file.to_string_lossy().to_string();
+    let profile = wizard.run().unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Profile DEFAULT already exists"));
+    assert!(output.contains("Invalid OCID bad"));
+    assert!(output.contains("The tenancy is in realm oc2, but the user in realm oc1"));
+    assert!(output.contains("eu- matches ") && output.contains("eu-frankfurt-1, "));
+    assert!(output.contains("Using region eu-frankfurt-1"));
+
+    assert_eq!(profile.name, "ADMIN");
+    let key = decode(common::PRIVATE_KEY, None).unwrap();
+    assert_eq!(profile.fingerprint, Some(fingerprint(&RsaPublicKey::from(&key)).unwrap()));
+    let config = Config::load(config_file.to_str().unwrap()).unwrap();
+    assert_eq!(config.get("ADMIN", "region"), Some("eu-frankfurt-1"));
+    assert_eq!(config.get("DEFAULT", "region"), Some("us-ashburn-1"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn generated_keys() {
+    let dir = common::temp_dir("wizard_keys");
+    let setup = |config: &str, reference: &str| {
+        let answers = format!(
+            "ADMIN\nocid1.user.oc1..aaaaaaaaxxxxxx\nocid1.tenancy.oc1..aaaaaaaayyyyyy\nFRA\ny\nsecret\n{}\n",
+            reference
+        );
+        let mut output: Vec<u8> = Vec::new();
+        let mut wizard = Wizard::new(answers.as_bytes(), &mut output);
+        wizard.config_file = dir.join(config).to_string_lossy().to_string();
+        wizard.key_dir = dir.to_string_lossy().to_string();
+        wizard.run().map(|_| String::from_utf8(output).unwrap())
+    };
+
+    let output = setup("config", "pass_phrase=secret\npass_phrase_env=OCI_CFG_TEST_WIZARD").unwrap();
+    assert!(output.contains("Use pass_phrase_env, pass_phrase_file or pass_phrase_command"));
+    assert!(output.contains(&format!("Key pair written to {}", dir.join("ADMIN").display())));
+    let config = Config::load(dir.join("config").to_str().unwrap()).unwrap();
+    assert_eq!(config.get("ADMIN", "pass_phrase"), None);
+    assert_eq!(config.get("ADMIN", "pass_phrase_env"), Some("OCI_CFG_TEST_WIZARD"));
+    let key_file = dir.join("ADMIN").join(PRIVATE_KEY);
+    assert_eq!(config.get("ADMIN", "key_file"), key_file.to_str());
+
+    // the keys of a profile with the same name are not replaced
+    let key = std::fs::read_to_string(&key_file).unwrap();
+    assert_eq!(setup("other", "").unwrap_err().kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(std::fs::read_to_string(&key_file).unwrap(), key);
+    std::fs::remove_dir_all(dir.join("ADMIN")).unwrap();
+    let output = setup("other", "").unwrap();
+    assert!(output.contains("The passphrase isn't stored"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cancelled_setup() {
+    let dir = common::temp_dir("wizard_cancelled");
+    let mut wizard = Wizard::new("ADMIN\n".as_bytes(), Vec::new());
+    wizard.config_file = dir.join("config").to_string_lossy().to_string();
+    assert_eq!(wizard.run().unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(!dir.join("config").exists());
+    std::fs::remove_dir_all(dir).unwrap();
+}